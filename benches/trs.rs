@@ -0,0 +1,24 @@
+//! Compares the cost of `Trs::matrix_fast` against `Trs::matrix`.
+//!
+//! Run with `cargo bench`.
+
+#[macro_use]
+extern crate euler;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+fn trs_matrix(c: &mut Criterion) {
+    let trs = trs!(
+        vec3!(1.0, -2.0, 3.0),
+        quat!(1.0, 2.0, 3.0; 0.75),
+        vec3!(2.0, 0.5, -3.0)
+    );
+    c.bench_function("Trs::matrix", |b| b.iter(|| black_box(&trs).matrix()));
+    c.bench_function("Trs::matrix_fast", |b| {
+        b.iter(|| black_box(&trs).matrix_fast())
+    });
+}
+
+criterion_group!(benches, trs_matrix);
+criterion_main!(benches);
@@ -6,15 +6,15 @@ use std::{f32, f64};
 fn unproject_single_precision(ndc: euler::Vec2) -> euler::Vec3 {
     let projection = mat4!();
     let inverse_projection = projection.inverse();
-    let eye = inverse_projection * vec4!(ndc, -1.0, 1.0);
-    let view = euler::Trs::new(
+    let eye = inverse_projection * euler::Vec4::point(vec3!(ndc, -1.0));
+    let view = trs!(
         vec3!(1, 0, -1),
         quat!(1, 0, 0; f32::consts::PI / 2.0),
-        vec3!(1.0),
+        vec3!(1.0)
     )
     .matrix();
     let inverse_view = view.inverse();
-    let world = inverse_view * vec4!(eye.xy(), -1.0, 0.0);
+    let world = inverse_view * euler::Vec4::direction(vec3!(eye.xy(), -1.0));
     let ray = world.xyz().normalize();
     ray
 }
@@ -22,15 +22,15 @@ fn unproject_single_precision(ndc: euler::Vec2) -> euler::Vec3 {
 fn unproject_double_precision(ndc: euler::Vec2) -> euler::DVec3 {
     let projection = dmat4!();
     let inverse_projection = projection.inverse();
-    let eye = inverse_projection * dvec4!(dvec2!(ndc), -1, 1);
-    let view = euler::DTrs::new(
+    let eye = inverse_projection * euler::DVec4::point(dvec3!(dvec2!(ndc), -1));
+    let view = dtrs!(
         dvec3!(1, 0, -1),
         dquat!(1, 0, 0; f64::consts::PI / 2.0),
-        dvec3!(1.0),
+        dvec3!(1.0)
     )
     .matrix();
     let inverse_view = view.inverse();
-    let world = inverse_view * dvec4!(eye.xy(), -1, 0);
+    let world = inverse_view * euler::DVec4::direction(dvec3!(eye.xy(), -1));
     let ray = world.xyz().normalize();
     ray
 }
@@ -6,16 +6,29 @@ extern crate approx;
 extern crate cgmath;
 #[cfg(feature = "mint")]
 extern crate mint;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "serde")]
+extern crate serde;
 
 #[macro_use]
 mod macros;
 
 mod mat;
 mod quat;
+#[cfg(feature = "rand")]
+mod rand_support;
+mod scalar;
+#[cfg(feature = "serde")]
+mod serde_support;
 mod trs;
 mod vec;
 
-pub use mat::{DMat2, DMat3, DMat4, Mat2, Mat3, Mat4};
-pub use quat::{DQuat, Quat};
+pub use mat::{DMat2, DMat3, DMat4, DepthRange, Mat2, Mat3, Mat4};
+pub use quat::{DQuat, EulerOrder, Quat};
+#[cfg(feature = "rand")]
+pub use rand_support::{UniformComponents, UnitSphere};
+#[cfg(feature = "serde")]
+pub use serde_support::{DTrsEulerRepr, TrsEulerRepr};
 pub use trs::{DTrs, Trs};
 pub use vec::{DVec2, DVec3, DVec4, Vec2, Vec3, Vec4};
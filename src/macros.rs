@@ -532,6 +532,110 @@ macro_rules! dquat {
     };
 }
 
+/// Single-precision translation + rotation + scale transform macro constructor.
+///
+/// Identity.
+///
+/// ```rust
+/// # #[macro_use] extern crate euler;
+/// # fn main() {
+/// let trs = trs!();
+/// assert_eq!(trs, euler::Trs::identity());
+/// # }
+/// ```
+///
+/// Translation, rotation, and scale.
+///
+/// ```rust
+/// # #[macro_use] extern crate euler;
+/// # fn main() {
+/// use std::f32::consts::PI;
+/// let view = trs!(vec3!(1, 0, -1), quat!(1, 0, 0; PI / 2.0), vec3!(1.0)).matrix();
+/// # assert_eq!(view.m30, 1.0);
+/// # }
+/// ```
+///
+/// Named translation, rotation, and scale.
+///
+/// ```rust
+/// # #[macro_use] extern crate euler;
+/// # fn main() {
+/// use std::f32::consts::PI;
+/// let trs = trs!(
+///     translation: vec3!(1, 0, -1),
+///     rotation: quat!(1, 0, 0; PI / 2.0),
+///     scale: vec3!(1.0)
+/// );
+/// assert_eq!(trs, trs!(vec3!(1, 0, -1), quat!(1, 0, 0; PI / 2.0), vec3!(1.0)));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! trs {
+    () => {
+        $crate::Trs::identity()
+    };
+
+    (translation: $t:expr, rotation: $r:expr, scale: $s:expr) => {
+        $crate::Trs::new($t, $r, $s)
+    };
+
+    ($t:expr, $r:expr, $s:expr) => {
+        $crate::Trs::new($t, $r, $s)
+    };
+}
+
+/// Double-precision translation + rotation + scale transform macro constructor.
+///
+/// Identity.
+///
+/// ```rust
+/// # #[macro_use] extern crate euler;
+/// # fn main() {
+/// let trs = dtrs!();
+/// assert_eq!(trs, euler::DTrs::identity());
+/// # }
+/// ```
+///
+/// Translation, rotation, and scale.
+///
+/// ```rust
+/// # #[macro_use] extern crate euler;
+/// # fn main() {
+/// use std::f64::consts::PI;
+/// let view = dtrs!(dvec3!(1, 0, -1), dquat!(1, 0, 0; PI / 2.0), dvec3!(1.0)).matrix();
+/// # assert_eq!(view.m30, 1.0);
+/// # }
+/// ```
+///
+/// Named translation, rotation, and scale.
+///
+/// ```rust
+/// # #[macro_use] extern crate euler;
+/// # fn main() {
+/// use std::f64::consts::PI;
+/// let trs = dtrs!(
+///     translation: dvec3!(1, 0, -1),
+///     rotation: dquat!(1, 0, 0; PI / 2.0),
+///     scale: dvec3!(1.0)
+/// );
+/// assert_eq!(trs, dtrs!(dvec3!(1, 0, -1), dquat!(1, 0, 0; PI / 2.0), dvec3!(1.0)));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! dtrs {
+    () => {
+        $crate::DTrs::identity()
+    };
+
+    (translation: $t:expr, rotation: $r:expr, scale: $s:expr) => {
+        $crate::DTrs::new($t, $r, $s)
+    };
+
+    ($t:expr, $r:expr, $s:expr) => {
+        $crate::DTrs::new($t, $r, $s)
+    };
+}
+
 /// Single-precision 2D vector macro constructor.
 ///
 /// # Examples
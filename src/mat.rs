@@ -1,7 +1,21 @@
-use crate::{DVec2, DVec3, DVec4, Vec2, Vec3, Vec4};
+use crate::scalar;
+use crate::{DQuat, DVec2, DVec3, DVec4, Quat, Vec2, Vec3, Vec4};
 use approx::ApproxEq;
+use approx05::AbsDiffEq;
 use cgmath;
-use std::{fmt, mem, ops};
+use std::{fmt, iter, mem, ops};
+
+/// Range of normalized device depth coordinates that projection matrices map
+/// the near and far clipping planes to.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum DepthRange {
+    /// OpenGL convention, with the near plane at -1 and the far plane at 1.
+    #[default]
+    NegativeOneToOne,
+    /// Direct3D, Metal, Vulkan, and wgpu convention, with the near plane at 0
+    /// and the far plane at 1.
+    ZeroToOne,
+}
 
 /// Single-precision 2x2 column major matrix.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -15,11 +29,7 @@ pub struct Mat2 {
 
 impl fmt::Display for Mat2 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "({}, {}; {}, {})",
-            self.m00, self.m01, self.m10, self.m11,
-        )
+        scalar::fmt_components(f, &self.to_cols_array(), 2, fmt::Display::fmt)
     }
 }
 
@@ -43,6 +53,56 @@ impl Mat2 {
     pub fn tridiagonal(lo: f32, di: f32, up: f32) -> Self {
         Mat2::new(di, up, lo, di)
     }
+
+    /// Column vectors constructor.
+    pub fn from_cols(c0: Vec2, c1: Vec2) -> Self {
+        Mat2::new(c0.x, c0.y, c1.x, c1.y)
+    }
+
+    /// Flat column-major array constructor.
+    pub fn from_cols_array(a: [f32; 4]) -> Self {
+        Mat2::new(a[0], a[1], a[2], a[3])
+    }
+
+    /// Constructor for a counter-clockwise rotation by `angle` radians.
+    pub fn from_angle(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Mat2::new(cos, sin, -sin, cos)
+    }
+
+    /// Constructor for a non-uniform scale by `v`.
+    pub fn from_scale(v: Vec2) -> Self {
+        Mat2::new(v.x, 0.0, 0.0, v.y)
+    }
+
+    /// Returns the matrix elements as a flat column-major array.
+    pub fn to_cols_array(self) -> [f32; 4] {
+        [self.m00, self.m01, self.m10, self.m11]
+    }
+
+    /// Returns the cofactor of the element at `(row, col)`, i.e. the signed
+    /// determinant of the matrix with that row and column removed.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if either index is out of range.
+    pub fn cofactor(self, row: usize, col: usize) -> f32 {
+        assert!(row < 2 && col < 2, "index out of range");
+        let minor = self[(1 - row, 1 - col)];
+        if row == col {
+            minor
+        } else {
+            -minor
+        }
+    }
+
+    /// Returns the adjugate matrix, the transpose of the matrix of cofactors.
+    ///
+    /// For an invertible matrix, the inverse is the adjugate divided by the
+    /// determinant.
+    pub fn adjugate(self) -> Self {
+        Mat2::new(self.m11, -self.m01, -self.m10, self.m00)
+    }
 }
 
 impl From<f32> for Mat2 {
@@ -104,11 +164,7 @@ pub struct DMat2 {
 
 impl fmt::Display for DMat2 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "({}, {}; {}, {})",
-            self.m00, self.m01, self.m10, self.m11,
-        )
+        scalar::fmt_components(f, &self.to_cols_array(), 2, fmt::Display::fmt)
     }
 }
 
@@ -132,6 +188,56 @@ impl DMat2 {
     pub fn tridiagonal(lo: f64, di: f64, up: f64) -> Self {
         DMat2::new(di, up, lo, di)
     }
+
+    /// Column vectors constructor.
+    pub fn from_cols(c0: DVec2, c1: DVec2) -> Self {
+        DMat2::new(c0.x, c0.y, c1.x, c1.y)
+    }
+
+    /// Flat column-major array constructor.
+    pub fn from_cols_array(a: [f64; 4]) -> Self {
+        DMat2::new(a[0], a[1], a[2], a[3])
+    }
+
+    /// Constructor for a counter-clockwise rotation by `angle` radians.
+    pub fn from_angle(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        DMat2::new(cos, sin, -sin, cos)
+    }
+
+    /// Constructor for a non-uniform scale by `v`.
+    pub fn from_scale(v: DVec2) -> Self {
+        DMat2::new(v.x, 0.0, 0.0, v.y)
+    }
+
+    /// Returns the matrix elements as a flat column-major array.
+    pub fn to_cols_array(self) -> [f64; 4] {
+        [self.m00, self.m01, self.m10, self.m11]
+    }
+
+    /// Returns the cofactor of the element at `(row, col)`, i.e. the signed
+    /// determinant of the matrix with that row and column removed.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if either index is out of range.
+    pub fn cofactor(self, row: usize, col: usize) -> f64 {
+        assert!(row < 2 && col < 2, "index out of range");
+        let minor = self[(1 - row, 1 - col)];
+        if row == col {
+            minor
+        } else {
+            -minor
+        }
+    }
+
+    /// Returns the adjugate matrix, the transpose of the matrix of cofactors.
+    ///
+    /// For an invertible matrix, the inverse is the adjugate divided by the
+    /// determinant.
+    pub fn adjugate(self) -> Self {
+        DMat2::new(self.m11, -self.m01, -self.m10, self.m00)
+    }
 }
 
 impl From<f32> for DMat2 {
@@ -198,19 +304,7 @@ pub struct Mat3 {
 
 impl fmt::Display for Mat3 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "({}, {}, {}; {}, {}, {}; {}, {}, {})",
-            self.m00,
-            self.m01,
-            self.m02,
-            self.m10,
-            self.m11,
-            self.m12,
-            self.m20,
-            self.m21,
-            self.m22,
-        )
+        scalar::fmt_components(f, &self.to_cols_array(), 3, fmt::Display::fmt)
     }
 }
 
@@ -254,6 +348,167 @@ impl Mat3 {
     pub fn tridiagonal(lo: f32, di: f32, up: f32) -> Self {
         Mat3::new(di, up, 0., lo, di, up, 0., lo, di)
     }
+
+    /// Column vectors constructor.
+    pub fn from_cols(c0: Vec3, c1: Vec3, c2: Vec3) -> Self {
+        Mat3::new(c0.x, c0.y, c0.z, c1.x, c1.y, c1.z, c2.x, c2.y, c2.z)
+    }
+
+    /// Flat column-major array constructor.
+    pub fn from_cols_array(a: [f32; 9]) -> Self {
+        Mat3::new(a[0], a[1], a[2], a[3], a[4], a[5], a[6], a[7], a[8])
+    }
+
+    /// Returns the matrix elements as a flat column-major array.
+    pub fn to_cols_array(self) -> [f32; 9] {
+        [
+            self.m00, self.m01, self.m02, self.m10, self.m11, self.m12, self.m20, self.m21,
+            self.m22,
+        ]
+    }
+
+    /// Constructor for a rotation around the X axis by `angle` radians.
+    pub fn from_angle_x(angle: f32) -> Self {
+        let m: [[f32; 3]; 3] = cgmath::Matrix3::from_angle_x(cgmath::Rad(angle)).into();
+        Mat3::from(m)
+    }
+
+    /// Constructor for a rotation around the Y axis by `angle` radians.
+    pub fn from_angle_y(angle: f32) -> Self {
+        let m: [[f32; 3]; 3] = cgmath::Matrix3::from_angle_y(cgmath::Rad(angle)).into();
+        Mat3::from(m)
+    }
+
+    /// Constructor for a rotation around the Z axis by `angle` radians.
+    pub fn from_angle_z(angle: f32) -> Self {
+        let m: [[f32; 3]; 3] = cgmath::Matrix3::from_angle_z(cgmath::Rad(angle)).into();
+        Mat3::from(m)
+    }
+
+    /// Constructor for a rotation around `axis` by `angle` radians.
+    ///
+    /// `axis` need not be normalized.
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let axis = axis.normalize();
+        let m: [[f32; 3]; 3] = cgmath::Matrix3::from_axis_angle(
+            cgmath::Vector3::new(axis.x, axis.y, axis.z),
+            cgmath::Rad(angle),
+        )
+        .into();
+        Mat3::from(m)
+    }
+
+    /// Constructor for a rotation whose negative Z axis points along `forward`
+    /// and whose Y axis is as close as possible to `up`.
+    ///
+    /// The columns of the result are the right, up, and backward directions,
+    /// matching the camera orientation of `look_at`. Neither argument need be
+    /// normalized. If `up` is parallel to `forward` then an arbitrary
+    /// perpendicular up direction is chosen.
+    pub fn look_rotation(forward: Vec3, up: Vec3) -> Self {
+        let f = forward.normalize();
+        let r = f.cross(up);
+        let r = if r.length() <= f32::EPSILON * up.length() {
+            f.any_orthonormal_pair().0
+        } else {
+            r.normalize()
+        };
+        Mat3::from_cols(r, r.cross(f), -f)
+    }
+
+    /// Constructor for a 2D affine translation by `v`.
+    pub fn from_translation2(v: Vec2) -> Self {
+        let mut m = Mat3::identity();
+        m.m20 = v.x;
+        m.m21 = v.y;
+        m
+    }
+
+    /// Constructor for a 2D affine non-uniform scale by `v`.
+    pub fn from_scale2(v: Vec2) -> Self {
+        let mut m = Mat3::identity();
+        m.m00 = v.x;
+        m.m11 = v.y;
+        m
+    }
+
+    /// Constructor for a 2D affine counter-clockwise rotation by `angle`
+    /// radians.
+    pub fn from_angle(angle: f32) -> Self {
+        Mat3::from_angle_z(angle)
+    }
+
+    /// Returns the 2D affine transformation of the point `p`.
+    ///
+    /// The point is extended with `w = 1` and the result is divided by its `w`
+    /// component.
+    pub fn transform_point2(self, p: Vec2) -> Vec2 {
+        let v = self * vec3!(p, 1.0);
+        v.xy() / v.z
+    }
+
+    /// Returns the 2D affine transformation of the direction vector `v`.
+    ///
+    /// The vector is extended with `w = 0`, hence translation does not apply.
+    pub fn transform_vector2(self, v: Vec2) -> Vec2 {
+        (self * vec3!(v, 0.0)).xy()
+    }
+
+    /// Returns the matrix with orthonormal columns closest in direction to the
+    /// columns of `self`, obtained by Gram-Schmidt orthonormalization.
+    ///
+    /// The first column keeps its direction, the second is made perpendicular
+    /// to the first, and the third to both. This is useful for removing drift
+    /// from rotation matrices after many accumulated operations.
+    pub fn orthonormalize(self) -> Self {
+        let c0 = self.column(0).normalize();
+        let c1 = self.column(1);
+        let c1 = (c1 - c0 * c0.dot(c1)).normalize();
+        let c2 = self.column(2);
+        let c2 = (c2 - c0 * c0.dot(c2) - c1 * c1.dot(c2)).normalize();
+        Mat3::from_cols(c0, c1, c2)
+    }
+
+    /// Returns `true` if the matrix preserves handedness, i.e. if its
+    /// determinant is positive.
+    ///
+    /// A matrix that mirrors an odd number of axes returns `false`.
+    pub fn is_right_handed(self) -> bool {
+        self.determinant() > 0.0
+    }
+
+    /// Returns the cofactor of the element at `(row, col)`, i.e. the signed
+    /// determinant of the matrix with that row and column removed.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if either index is out of range.
+    pub fn cofactor(self, row: usize, col: usize) -> f32 {
+        assert!(row < 3 && col < 3, "index out of range");
+        // Taking the remaining rows and columns in cyclic order accounts for
+        // the sign of the cofactor.
+        let (r0, r1) = ((row + 1) % 3, (row + 2) % 3);
+        let (c0, c1) = ((col + 1) % 3, (col + 2) % 3);
+        self[(r0, c0)] * self[(r1, c1)] - self[(r0, c1)] * self[(r1, c0)]
+    }
+
+    /// Returns the adjugate matrix, the transpose of the matrix of cofactors.
+    ///
+    /// For an invertible matrix, the inverse is the adjugate divided by the
+    /// determinant.
+    pub fn adjugate(self) -> Self {
+        Mat3::from_cols_array([
+            self.cofactor(0, 0),
+            self.cofactor(0, 1),
+            self.cofactor(0, 2),
+            self.cofactor(1, 0),
+            self.cofactor(1, 1),
+            self.cofactor(1, 2),
+            self.cofactor(2, 0),
+            self.cofactor(2, 1),
+            self.cofactor(2, 2),
+        ])
+    }
 }
 
 impl From<f32> for Mat3 {
@@ -337,19 +592,7 @@ pub struct DMat3 {
 
 impl fmt::Display for DMat3 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "({}, {}, {}; {}, {}, {}; {}, {}, {})",
-            self.m00,
-            self.m01,
-            self.m02,
-            self.m10,
-            self.m11,
-            self.m12,
-            self.m20,
-            self.m21,
-            self.m22,
-        )
+        scalar::fmt_components(f, &self.to_cols_array(), 3, fmt::Display::fmt)
     }
 }
 
@@ -393,6 +636,167 @@ impl DMat3 {
     pub fn tridiagonal(lo: f64, di: f64, up: f64) -> Self {
         DMat3::new(di, up, 0., lo, di, up, 0., lo, di)
     }
+
+    /// Column vectors constructor.
+    pub fn from_cols(c0: DVec3, c1: DVec3, c2: DVec3) -> Self {
+        DMat3::new(c0.x, c0.y, c0.z, c1.x, c1.y, c1.z, c2.x, c2.y, c2.z)
+    }
+
+    /// Flat column-major array constructor.
+    pub fn from_cols_array(a: [f64; 9]) -> Self {
+        DMat3::new(a[0], a[1], a[2], a[3], a[4], a[5], a[6], a[7], a[8])
+    }
+
+    /// Returns the matrix elements as a flat column-major array.
+    pub fn to_cols_array(self) -> [f64; 9] {
+        [
+            self.m00, self.m01, self.m02, self.m10, self.m11, self.m12, self.m20, self.m21,
+            self.m22,
+        ]
+    }
+
+    /// Constructor for a rotation around the X axis by `angle` radians.
+    pub fn from_angle_x(angle: f64) -> Self {
+        let m: [[f64; 3]; 3] = cgmath::Matrix3::from_angle_x(cgmath::Rad(angle)).into();
+        DMat3::from(m)
+    }
+
+    /// Constructor for a rotation around the Y axis by `angle` radians.
+    pub fn from_angle_y(angle: f64) -> Self {
+        let m: [[f64; 3]; 3] = cgmath::Matrix3::from_angle_y(cgmath::Rad(angle)).into();
+        DMat3::from(m)
+    }
+
+    /// Constructor for a rotation around the Z axis by `angle` radians.
+    pub fn from_angle_z(angle: f64) -> Self {
+        let m: [[f64; 3]; 3] = cgmath::Matrix3::from_angle_z(cgmath::Rad(angle)).into();
+        DMat3::from(m)
+    }
+
+    /// Constructor for a rotation around `axis` by `angle` radians.
+    ///
+    /// `axis` need not be normalized.
+    pub fn from_axis_angle(axis: DVec3, angle: f64) -> Self {
+        let axis = axis.normalize();
+        let m: [[f64; 3]; 3] = cgmath::Matrix3::from_axis_angle(
+            cgmath::Vector3::new(axis.x, axis.y, axis.z),
+            cgmath::Rad(angle),
+        )
+        .into();
+        DMat3::from(m)
+    }
+
+    /// Constructor for a rotation whose negative Z axis points along `forward`
+    /// and whose Y axis is as close as possible to `up`.
+    ///
+    /// The columns of the result are the right, up, and backward directions,
+    /// matching the camera orientation of `look_at`. Neither argument need be
+    /// normalized. If `up` is parallel to `forward` then an arbitrary
+    /// perpendicular up direction is chosen.
+    pub fn look_rotation(forward: DVec3, up: DVec3) -> Self {
+        let f = forward.normalize();
+        let r = f.cross(up);
+        let r = if r.length() <= f64::EPSILON * up.length() {
+            f.any_orthonormal_pair().0
+        } else {
+            r.normalize()
+        };
+        DMat3::from_cols(r, r.cross(f), -f)
+    }
+
+    /// Constructor for a 2D affine translation by `v`.
+    pub fn from_translation2(v: DVec2) -> Self {
+        let mut m = DMat3::identity();
+        m.m20 = v.x;
+        m.m21 = v.y;
+        m
+    }
+
+    /// Constructor for a 2D affine non-uniform scale by `v`.
+    pub fn from_scale2(v: DVec2) -> Self {
+        let mut m = DMat3::identity();
+        m.m00 = v.x;
+        m.m11 = v.y;
+        m
+    }
+
+    /// Constructor for a 2D affine counter-clockwise rotation by `angle`
+    /// radians.
+    pub fn from_angle(angle: f64) -> Self {
+        DMat3::from_angle_z(angle)
+    }
+
+    /// Returns the 2D affine transformation of the point `p`.
+    ///
+    /// The point is extended with `w = 1` and the result is divided by its `w`
+    /// component.
+    pub fn transform_point2(self, p: DVec2) -> DVec2 {
+        let v = self * dvec3!(p, 1.0);
+        v.xy() / v.z
+    }
+
+    /// Returns the 2D affine transformation of the direction vector `v`.
+    ///
+    /// The vector is extended with `w = 0`, hence translation does not apply.
+    pub fn transform_vector2(self, v: DVec2) -> DVec2 {
+        (self * dvec3!(v, 0.0)).xy()
+    }
+
+    /// Returns the matrix with orthonormal columns closest in direction to the
+    /// columns of `self`, obtained by Gram-Schmidt orthonormalization.
+    ///
+    /// The first column keeps its direction, the second is made perpendicular
+    /// to the first, and the third to both. This is useful for removing drift
+    /// from rotation matrices after many accumulated operations.
+    pub fn orthonormalize(self) -> Self {
+        let c0 = self.column(0).normalize();
+        let c1 = self.column(1);
+        let c1 = (c1 - c0 * c0.dot(c1)).normalize();
+        let c2 = self.column(2);
+        let c2 = (c2 - c0 * c0.dot(c2) - c1 * c1.dot(c2)).normalize();
+        DMat3::from_cols(c0, c1, c2)
+    }
+
+    /// Returns `true` if the matrix preserves handedness, i.e. if its
+    /// determinant is positive.
+    ///
+    /// A matrix that mirrors an odd number of axes returns `false`.
+    pub fn is_right_handed(self) -> bool {
+        self.determinant() > 0.0
+    }
+
+    /// Returns the cofactor of the element at `(row, col)`, i.e. the signed
+    /// determinant of the matrix with that row and column removed.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if either index is out of range.
+    pub fn cofactor(self, row: usize, col: usize) -> f64 {
+        assert!(row < 3 && col < 3, "index out of range");
+        // Taking the remaining rows and columns in cyclic order accounts for
+        // the sign of the cofactor.
+        let (r0, r1) = ((row + 1) % 3, (row + 2) % 3);
+        let (c0, c1) = ((col + 1) % 3, (col + 2) % 3);
+        self[(r0, c0)] * self[(r1, c1)] - self[(r0, c1)] * self[(r1, c0)]
+    }
+
+    /// Returns the adjugate matrix, the transpose of the matrix of cofactors.
+    ///
+    /// For an invertible matrix, the inverse is the adjugate divided by the
+    /// determinant.
+    pub fn adjugate(self) -> Self {
+        DMat3::from_cols_array([
+            self.cofactor(0, 0),
+            self.cofactor(0, 1),
+            self.cofactor(0, 2),
+            self.cofactor(1, 0),
+            self.cofactor(1, 1),
+            self.cofactor(1, 2),
+            self.cofactor(2, 0),
+            self.cofactor(2, 1),
+            self.cofactor(2, 2),
+        ])
+    }
 }
 
 impl From<f32> for DMat3 {
@@ -473,26 +877,7 @@ pub struct Mat4 {
 
 impl fmt::Display for Mat4 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "({}, {}, {}, {}; {}, {}, {}, {}; {}, {}, {}, {}; {}, {}, {}, {})",
-            self.m00,
-            self.m01,
-            self.m02,
-            self.m03,
-            self.m10,
-            self.m11,
-            self.m12,
-            self.m13,
-            self.m20,
-            self.m21,
-            self.m22,
-            self.m23,
-            self.m30,
-            self.m31,
-            self.m32,
-            self.m33,
-        )
+        scalar::fmt_components(f, &self.to_cols_array(), 4, fmt::Display::fmt)
     }
 }
 
@@ -552,6 +937,367 @@ impl Mat4 {
             di, up, 0., 0., lo, di, up, 0., 0., lo, di, up, 0., 0., lo, di,
         )
     }
+
+    /// Column vectors constructor.
+    pub fn from_cols(c0: Vec4, c1: Vec4, c2: Vec4, c3: Vec4) -> Self {
+        Mat4::new(
+            c0.x, c0.y, c0.z, c0.w, c1.x, c1.y, c1.z, c1.w, c2.x, c2.y, c2.z, c2.w, c3.x, c3.y,
+            c3.z, c3.w,
+        )
+    }
+
+    /// Flat column-major array constructor.
+    pub fn from_cols_array(a: [f32; 16]) -> Self {
+        Mat4::new(
+            a[0], a[1], a[2], a[3], a[4], a[5], a[6], a[7], a[8], a[9], a[10], a[11], a[12], a[13],
+            a[14], a[15],
+        )
+    }
+
+    /// Returns the matrix elements as a flat column-major array.
+    pub fn to_cols_array(self) -> [f32; 16] {
+        [
+            self.m00, self.m01, self.m02, self.m03, self.m10, self.m11, self.m12, self.m13,
+            self.m20, self.m21, self.m22, self.m23, self.m30, self.m31, self.m32, self.m33,
+        ]
+    }
+
+    /// Constructor for a rotation around the X axis by `angle` radians.
+    pub fn from_angle_x(angle: f32) -> Self {
+        let m: [[f32; 4]; 4] = cgmath::Matrix4::from_angle_x(cgmath::Rad(angle)).into();
+        Mat4::from(m)
+    }
+
+    /// Constructor for a rotation around the Y axis by `angle` radians.
+    pub fn from_angle_y(angle: f32) -> Self {
+        let m: [[f32; 4]; 4] = cgmath::Matrix4::from_angle_y(cgmath::Rad(angle)).into();
+        Mat4::from(m)
+    }
+
+    /// Constructor for a rotation around the Z axis by `angle` radians.
+    pub fn from_angle_z(angle: f32) -> Self {
+        let m: [[f32; 4]; 4] = cgmath::Matrix4::from_angle_z(cgmath::Rad(angle)).into();
+        Mat4::from(m)
+    }
+
+    /// Constructor for a rotation around `axis` by `angle` radians.
+    ///
+    /// `axis` need not be normalized.
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let axis = axis.normalize();
+        let m: [[f32; 4]; 4] = cgmath::Matrix4::from_axis_angle(
+            cgmath::Vector3::new(axis.x, axis.y, axis.z),
+            cgmath::Rad(angle),
+        )
+        .into();
+        Mat4::from(m)
+    }
+
+    /// Constructor for a non-uniform scale by `scale` followed by a
+    /// translation by `translation`, without rotation.
+    pub fn from_scale_translation(scale: Vec3, translation: Vec3) -> Self {
+        let mut m = Mat4::identity();
+        m.m00 = scale.x;
+        m.m11 = scale.y;
+        m.m22 = scale.z;
+        m.m30 = translation.x;
+        m.m31 = translation.y;
+        m.m32 = translation.z;
+        m
+    }
+
+    /// Constructor for a reflection about the plane of points `p` satisfying
+    /// `plane_normal · p + plane_d = 0`.
+    ///
+    /// `plane_normal` must be normalized, in which case the plane lies at a
+    /// signed distance of `-plane_d` from the origin along `plane_normal`.
+    pub fn reflection(plane_normal: Vec3, plane_d: f32) -> Self {
+        let (a, b, c, d) = (plane_normal.x, plane_normal.y, plane_normal.z, plane_d);
+        Mat4::new(
+            1.0 - 2.0 * a * a,
+            -2.0 * a * b,
+            -2.0 * a * c,
+            0.0,
+            -2.0 * a * b,
+            1.0 - 2.0 * b * b,
+            -2.0 * b * c,
+            0.0,
+            -2.0 * a * c,
+            -2.0 * b * c,
+            1.0 - 2.0 * c * c,
+            0.0,
+            -2.0 * a * d,
+            -2.0 * b * d,
+            -2.0 * c * d,
+            1.0,
+        )
+    }
+
+    /// Right-handed view matrix constructor.
+    ///
+    /// The resulting matrix transforms `eye` to the origin and the direction
+    /// from `eye` towards `center` to the negative Z axis.
+    pub fn look_at(eye: Vec3, center: Vec3, up: Vec3) -> Self {
+        let m: [[f32; 4]; 4] = cgmath::Matrix4::look_at(
+            cgmath::Point3::new(eye.x, eye.y, eye.z),
+            cgmath::Point3::new(center.x, center.y, center.z),
+            cgmath::Vector3::new(up.x, up.y, up.z),
+        )
+        .into();
+        Mat4::from(m)
+    }
+
+    /// Computes the normal matrix, i.e. the inverse transpose of the upper-left
+    /// 3x3 block, used to transform surface normals.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the upper-left 3x3 block has no inverse.
+    pub fn normal_matrix(self) -> Mat3 {
+        self.try_normal_matrix().unwrap()
+    }
+
+    /// Attempts to compute the normal matrix, returning `None` if the upper-left
+    /// 3x3 block is non-invertible.
+    pub fn try_normal_matrix(self) -> Option<Mat3> {
+        Mat3::from(self).try_invert().map(Mat3::transpose)
+    }
+
+    /// Returns the matrix with the columns of its upper-left 3x3 block
+    /// orthonormalized, as per `Mat3::orthonormalize`.
+    ///
+    /// The translation and the bottom row are unchanged.
+    pub fn orthonormalize(self) -> Self {
+        let r = Mat3::from(self).orthonormalize();
+        let mut m = self;
+        m.m00 = r.m00;
+        m.m01 = r.m01;
+        m.m02 = r.m02;
+        m.m10 = r.m10;
+        m.m11 = r.m11;
+        m.m12 = r.m12;
+        m.m20 = r.m20;
+        m.m21 = r.m21;
+        m.m22 = r.m22;
+        m
+    }
+
+    /// Returns `true` if the matrix preserves handedness, i.e. if its
+    /// determinant is positive.
+    ///
+    /// A transform that mirrors an odd number of axes returns `false`.
+    pub fn is_right_handed(self) -> bool {
+        self.determinant() > 0.0
+    }
+
+    /// Decomposes an affine transformation matrix into its translation,
+    /// rotation, and non-uniform scale components.
+    ///
+    /// If the matrix has a negative determinant (i.e. contains a mirror) then
+    /// the X axis scale factor is negated. If any scale factor is zero then the
+    /// rotation cannot be recovered and the identity rotation is returned.
+    pub fn decompose(self) -> (Vec3, Quat, Vec3) {
+        let t = vec3!(self.m30, self.m31, self.m32);
+        let mut s = Vec3::new(
+            vec3!(self.m00, self.m01, self.m02).length(),
+            vec3!(self.m10, self.m11, self.m12).length(),
+            vec3!(self.m20, self.m21, self.m22).length(),
+        );
+        if Mat3::from(self).determinant() < 0.0 {
+            s.x = -s.x;
+        }
+        if s.x == 0.0 || s.y == 0.0 || s.z == 0.0 {
+            return (t, quat!(), s);
+        }
+        let r = cgmath::Quaternion::from(cgmath::Matrix3::new(
+            self.m00 / s.x,
+            self.m01 / s.x,
+            self.m02 / s.x,
+            self.m10 / s.y,
+            self.m11 / s.y,
+            self.m12 / s.y,
+            self.m20 / s.z,
+            self.m21 / s.z,
+            self.m22 / s.z,
+        ));
+        (t, Quat::new(r.v.x, r.v.y, r.v.z, r.s), s)
+    }
+
+    /// Returns the transformation of the point `p`.
+    ///
+    /// The point is extended with `w = 1` and the result is divided by its `w`
+    /// component.
+    pub fn transform_point(self, p: Vec3) -> Vec3 {
+        let v = self * vec4!(p, 1.0);
+        v.xyz() / v.w
+    }
+
+    /// Writes the transformation of each point in `points` to the corresponding
+    /// element of `out`, as per `transform_point`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `points` and `out` have different lengths.
+    pub fn transform_points(self, points: &[Vec3], out: &mut [Vec3]) {
+        assert_eq!(points.len(), out.len(), "slice length mismatch");
+        for (p, o) in points.iter().zip(out.iter_mut()) {
+            *o = self.transform_point(*p);
+        }
+    }
+
+    /// Returns the transformation of the direction vector `v`.
+    ///
+    /// The vector is extended with `w = 0`, hence translation does not apply.
+    pub fn transform_vector(self, v: Vec3) -> Vec3 {
+        (self * vec4!(v, 0.0)).xyz()
+    }
+
+    /// Returns the transformation of the ray starting at `origin` in the
+    /// direction `dir`, as a `(origin, direction)` pair.
+    ///
+    /// The origin is transformed as per `transform_point` and the direction as
+    /// per `transform_vector`. The direction is not normalized, so distances
+    /// along the ray stay comparable with those along the transformed ray.
+    pub fn transform_ray(self, origin: Vec3, dir: Vec3) -> (Vec3, Vec3) {
+        (self.transform_point(origin), self.transform_vector(dir))
+    }
+
+    /// Right-handed perspective projection constructor.
+    ///
+    /// `fovy` is the vertical field of view in radians and `aspect` is the
+    /// ratio of width to height. Following OpenGL conventions, the near and far
+    /// clipping planes map to -1 and 1 respectively in normalized device
+    /// coordinates.
+    pub fn perspective(fovy: f32, aspect: f32, near: f32, far: f32) -> Self {
+        Mat4::perspective_with(fovy, aspect, near, far, DepthRange::NegativeOneToOne)
+    }
+
+    /// Right-handed perspective projection constructor, mapping the near and
+    /// far clipping planes to the given normalized device depth range.
+    ///
+    /// `fovy` is the vertical field of view in radians and `aspect` is the
+    /// ratio of width to height.
+    pub fn perspective_with(
+        fovy: f32,
+        aspect: f32,
+        near: f32,
+        far: f32,
+        range: DepthRange,
+    ) -> Self {
+        let m: [[f32; 4]; 4] = cgmath::perspective(cgmath::Rad(fovy), aspect, near, far).into();
+        let mut m = Mat4::from(m);
+        if range == DepthRange::ZeroToOne {
+            m.m22 = far / (near - far);
+            m.m32 = near * far / (near - far);
+        }
+        m
+    }
+
+    /// Right-handed perspective projection constructor for a frustum that is not
+    /// necessarily symmetric, such as for stereo rendering.
+    ///
+    /// `left`, `right`, `bottom`, and `top` are the bounds of the near clipping
+    /// plane at distance `near` from the eye. Following OpenGL conventions, the
+    /// near and far clipping planes map to -1 and 1 respectively in normalized
+    /// device coordinates.
+    pub fn frustum(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Self {
+        let m: [[f32; 4]; 4] = cgmath::frustum(left, right, bottom, top, near, far).into();
+        Mat4::from(m)
+    }
+
+    /// Converts a projection matrix to or from reverse-Z by negating the depth
+    /// output, such that the near and far planes are swapped in normalized
+    /// device coordinates.
+    ///
+    /// Assumes the OpenGL depth range of `[-1, 1]`. Applying the conversion
+    /// twice yields the original matrix.
+    pub fn to_reverse_z(self) -> Self {
+        self.to_reverse_z_with(DepthRange::NegativeOneToOne)
+    }
+
+    /// Converts a projection matrix mapping to the given normalized device
+    /// depth range to or from reverse-Z, such that the near and far planes are
+    /// swapped.
+    ///
+    /// Depth `d` becomes `-d` for `[-1, 1]` and `1 - d` for `[0, 1]`. Applying
+    /// the conversion twice yields the original matrix.
+    pub fn to_reverse_z_with(self, range: DepthRange) -> Self {
+        let mut flip = Mat4::identity();
+        flip.m22 = -1.0;
+        if range == DepthRange::ZeroToOne {
+            flip.m32 = 1.0;
+        }
+        flip * self
+    }
+
+    /// Recovers the `(fovy, aspect, near, far)` parameters of a perspective
+    /// projection matrix.
+    ///
+    /// Assumes the OpenGL depth range of `[-1, 1]`; a matrix constructed with
+    /// `DepthRange::ZeroToOne` has the same form but yields wrong near and far
+    /// values, and must be passed to `perspective_params_with` instead.
+    /// Returns `None` if the matrix does not have the form of a matrix
+    /// constructed by `perspective`.
+    pub fn perspective_params(self) -> Option<(f32, f32, f32, f32)> {
+        self.perspective_params_with(DepthRange::NegativeOneToOne)
+    }
+
+    /// Recovers the `(fovy, aspect, near, far)` parameters of a perspective
+    /// projection matrix mapping to the given normalized device depth range.
+    ///
+    /// The depth range cannot be recovered from the matrix itself, since both
+    /// forms yield plausible parameters; it must match the range the matrix was
+    /// constructed with. Returns `None` if the matrix does not have the form of
+    /// a matrix constructed by `perspective_with`.
+    pub fn perspective_params_with(self, range: DepthRange) -> Option<(f32, f32, f32, f32)> {
+        let zeros = [
+            self.m01, self.m02, self.m03, self.m10, self.m12, self.m13, self.m20, self.m21,
+            self.m30, self.m31, self.m33,
+        ];
+        if zeros.iter().any(|&x| x != 0.0) || self.m23 != -1.0 {
+            return None;
+        }
+        let fovy = 2.0 * (1.0 / self.m11).atan();
+        let aspect = self.m11 / self.m00;
+        let near = match range {
+            DepthRange::NegativeOneToOne => self.m32 / (self.m22 - 1.0),
+            DepthRange::ZeroToOne => self.m32 / self.m22,
+        };
+        let far = self.m32 / (self.m22 + 1.0);
+        if self.m00 > 0.0 && self.m11 > 0.0 && near > 0.0 && far > near {
+            Some((fovy, aspect, near, far))
+        } else {
+            None
+        }
+    }
+
+    /// Left-handed view matrix constructor.
+    ///
+    /// The resulting matrix transforms `eye` to the origin and the direction
+    /// from `eye` towards `center` to the positive Z axis.
+    pub fn look_at_lh(eye: Vec3, center: Vec3, up: Vec3) -> Self {
+        let f = (center - eye).normalize();
+        let s = up.cross(f).normalize();
+        let u = f.cross(s);
+        Mat4::new(
+            s.x,
+            u.x,
+            f.x,
+            0.0,
+            s.y,
+            u.y,
+            f.y,
+            0.0,
+            s.z,
+            u.z,
+            f.z,
+            0.0,
+            -s.dot(eye),
+            -u.dot(eye),
+            -f.dot(eye),
+            1.0,
+        )
+    }
 }
 
 impl From<f32> for Mat4 {
@@ -640,26 +1386,7 @@ pub struct DMat4 {
 
 impl fmt::Display for DMat4 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "({}, {}, {}, {}; {}, {}, {}, {}; {}, {}, {}, {}; {}, {}, {}, {})",
-            self.m00,
-            self.m01,
-            self.m02,
-            self.m03,
-            self.m10,
-            self.m11,
-            self.m12,
-            self.m13,
-            self.m20,
-            self.m21,
-            self.m22,
-            self.m23,
-            self.m30,
-            self.m31,
-            self.m32,
-            self.m33,
-        )
+        scalar::fmt_components(f, &self.to_cols_array(), 4, fmt::Display::fmt)
     }
 }
 
@@ -719,6 +1446,367 @@ impl DMat4 {
             di, up, 0., 0., lo, di, up, 0., 0., lo, di, up, 0., 0., lo, di,
         )
     }
+
+    /// Column vectors constructor.
+    pub fn from_cols(c0: DVec4, c1: DVec4, c2: DVec4, c3: DVec4) -> Self {
+        DMat4::new(
+            c0.x, c0.y, c0.z, c0.w, c1.x, c1.y, c1.z, c1.w, c2.x, c2.y, c2.z, c2.w, c3.x, c3.y,
+            c3.z, c3.w,
+        )
+    }
+
+    /// Flat column-major array constructor.
+    pub fn from_cols_array(a: [f64; 16]) -> Self {
+        DMat4::new(
+            a[0], a[1], a[2], a[3], a[4], a[5], a[6], a[7], a[8], a[9], a[10], a[11], a[12], a[13],
+            a[14], a[15],
+        )
+    }
+
+    /// Returns the matrix elements as a flat column-major array.
+    pub fn to_cols_array(self) -> [f64; 16] {
+        [
+            self.m00, self.m01, self.m02, self.m03, self.m10, self.m11, self.m12, self.m13,
+            self.m20, self.m21, self.m22, self.m23, self.m30, self.m31, self.m32, self.m33,
+        ]
+    }
+
+    /// Constructor for a rotation around the X axis by `angle` radians.
+    pub fn from_angle_x(angle: f64) -> Self {
+        let m: [[f64; 4]; 4] = cgmath::Matrix4::from_angle_x(cgmath::Rad(angle)).into();
+        DMat4::from(m)
+    }
+
+    /// Constructor for a rotation around the Y axis by `angle` radians.
+    pub fn from_angle_y(angle: f64) -> Self {
+        let m: [[f64; 4]; 4] = cgmath::Matrix4::from_angle_y(cgmath::Rad(angle)).into();
+        DMat4::from(m)
+    }
+
+    /// Constructor for a rotation around the Z axis by `angle` radians.
+    pub fn from_angle_z(angle: f64) -> Self {
+        let m: [[f64; 4]; 4] = cgmath::Matrix4::from_angle_z(cgmath::Rad(angle)).into();
+        DMat4::from(m)
+    }
+
+    /// Constructor for a rotation around `axis` by `angle` radians.
+    ///
+    /// `axis` need not be normalized.
+    pub fn from_axis_angle(axis: DVec3, angle: f64) -> Self {
+        let axis = axis.normalize();
+        let m: [[f64; 4]; 4] = cgmath::Matrix4::from_axis_angle(
+            cgmath::Vector3::new(axis.x, axis.y, axis.z),
+            cgmath::Rad(angle),
+        )
+        .into();
+        DMat4::from(m)
+    }
+
+    /// Constructor for a non-uniform scale by `scale` followed by a
+    /// translation by `translation`, without rotation.
+    pub fn from_scale_translation(scale: DVec3, translation: DVec3) -> Self {
+        let mut m = DMat4::identity();
+        m.m00 = scale.x;
+        m.m11 = scale.y;
+        m.m22 = scale.z;
+        m.m30 = translation.x;
+        m.m31 = translation.y;
+        m.m32 = translation.z;
+        m
+    }
+
+    /// Constructor for a reflection about the plane of points `p` satisfying
+    /// `plane_normal · p + plane_d = 0`.
+    ///
+    /// `plane_normal` must be normalized, in which case the plane lies at a
+    /// signed distance of `-plane_d` from the origin along `plane_normal`.
+    pub fn reflection(plane_normal: DVec3, plane_d: f64) -> Self {
+        let (a, b, c, d) = (plane_normal.x, plane_normal.y, plane_normal.z, plane_d);
+        DMat4::new(
+            1.0 - 2.0 * a * a,
+            -2.0 * a * b,
+            -2.0 * a * c,
+            0.0,
+            -2.0 * a * b,
+            1.0 - 2.0 * b * b,
+            -2.0 * b * c,
+            0.0,
+            -2.0 * a * c,
+            -2.0 * b * c,
+            1.0 - 2.0 * c * c,
+            0.0,
+            -2.0 * a * d,
+            -2.0 * b * d,
+            -2.0 * c * d,
+            1.0,
+        )
+    }
+
+    /// Right-handed view matrix constructor.
+    ///
+    /// The resulting matrix transforms `eye` to the origin and the direction
+    /// from `eye` towards `center` to the negative Z axis.
+    pub fn look_at(eye: DVec3, center: DVec3, up: DVec3) -> Self {
+        let m: [[f64; 4]; 4] = cgmath::Matrix4::look_at(
+            cgmath::Point3::new(eye.x, eye.y, eye.z),
+            cgmath::Point3::new(center.x, center.y, center.z),
+            cgmath::Vector3::new(up.x, up.y, up.z),
+        )
+        .into();
+        DMat4::from(m)
+    }
+
+    /// Computes the normal matrix, i.e. the inverse transpose of the upper-left
+    /// 3x3 block, used to transform surface normals.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the upper-left 3x3 block has no inverse.
+    pub fn normal_matrix(self) -> DMat3 {
+        self.try_normal_matrix().unwrap()
+    }
+
+    /// Attempts to compute the normal matrix, returning `None` if the upper-left
+    /// 3x3 block is non-invertible.
+    pub fn try_normal_matrix(self) -> Option<DMat3> {
+        DMat3::from(self).try_invert().map(DMat3::transpose)
+    }
+
+    /// Returns the matrix with the columns of its upper-left 3x3 block
+    /// orthonormalized, as per `DMat3::orthonormalize`.
+    ///
+    /// The translation and the bottom row are unchanged.
+    pub fn orthonormalize(self) -> Self {
+        let r = DMat3::from(self).orthonormalize();
+        let mut m = self;
+        m.m00 = r.m00;
+        m.m01 = r.m01;
+        m.m02 = r.m02;
+        m.m10 = r.m10;
+        m.m11 = r.m11;
+        m.m12 = r.m12;
+        m.m20 = r.m20;
+        m.m21 = r.m21;
+        m.m22 = r.m22;
+        m
+    }
+
+    /// Returns `true` if the matrix preserves handedness, i.e. if its
+    /// determinant is positive.
+    ///
+    /// A transform that mirrors an odd number of axes returns `false`.
+    pub fn is_right_handed(self) -> bool {
+        self.determinant() > 0.0
+    }
+
+    /// Decomposes an affine transformation matrix into its translation,
+    /// rotation, and non-uniform scale components.
+    ///
+    /// If the matrix has a negative determinant (i.e. contains a mirror) then
+    /// the X axis scale factor is negated. If any scale factor is zero then the
+    /// rotation cannot be recovered and the identity rotation is returned.
+    pub fn decompose(self) -> (DVec3, DQuat, DVec3) {
+        let t = dvec3!(self.m30, self.m31, self.m32);
+        let mut s = DVec3::new(
+            dvec3!(self.m00, self.m01, self.m02).length(),
+            dvec3!(self.m10, self.m11, self.m12).length(),
+            dvec3!(self.m20, self.m21, self.m22).length(),
+        );
+        if DMat3::from(self).determinant() < 0.0 {
+            s.x = -s.x;
+        }
+        if s.x == 0.0 || s.y == 0.0 || s.z == 0.0 {
+            return (t, dquat!(), s);
+        }
+        let r = cgmath::Quaternion::from(cgmath::Matrix3::new(
+            self.m00 / s.x,
+            self.m01 / s.x,
+            self.m02 / s.x,
+            self.m10 / s.y,
+            self.m11 / s.y,
+            self.m12 / s.y,
+            self.m20 / s.z,
+            self.m21 / s.z,
+            self.m22 / s.z,
+        ));
+        (t, DQuat::new(r.v.x, r.v.y, r.v.z, r.s), s)
+    }
+
+    /// Returns the transformation of the point `p`.
+    ///
+    /// The point is extended with `w = 1` and the result is divided by its `w`
+    /// component.
+    pub fn transform_point(self, p: DVec3) -> DVec3 {
+        let v = self * dvec4!(p, 1.0);
+        v.xyz() / v.w
+    }
+
+    /// Writes the transformation of each point in `points` to the corresponding
+    /// element of `out`, as per `transform_point`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `points` and `out` have different lengths.
+    pub fn transform_points(self, points: &[DVec3], out: &mut [DVec3]) {
+        assert_eq!(points.len(), out.len(), "slice length mismatch");
+        for (p, o) in points.iter().zip(out.iter_mut()) {
+            *o = self.transform_point(*p);
+        }
+    }
+
+    /// Returns the transformation of the direction vector `v`.
+    ///
+    /// The vector is extended with `w = 0`, hence translation does not apply.
+    pub fn transform_vector(self, v: DVec3) -> DVec3 {
+        (self * dvec4!(v, 0.0)).xyz()
+    }
+
+    /// Returns the transformation of the ray starting at `origin` in the
+    /// direction `dir`, as a `(origin, direction)` pair.
+    ///
+    /// The origin is transformed as per `transform_point` and the direction as
+    /// per `transform_vector`. The direction is not normalized, so distances
+    /// along the ray stay comparable with those along the transformed ray.
+    pub fn transform_ray(self, origin: DVec3, dir: DVec3) -> (DVec3, DVec3) {
+        (self.transform_point(origin), self.transform_vector(dir))
+    }
+
+    /// Right-handed perspective projection constructor.
+    ///
+    /// `fovy` is the vertical field of view in radians and `aspect` is the
+    /// ratio of width to height. Following OpenGL conventions, the near and far
+    /// clipping planes map to -1 and 1 respectively in normalized device
+    /// coordinates.
+    pub fn perspective(fovy: f64, aspect: f64, near: f64, far: f64) -> Self {
+        DMat4::perspective_with(fovy, aspect, near, far, DepthRange::NegativeOneToOne)
+    }
+
+    /// Right-handed perspective projection constructor, mapping the near and
+    /// far clipping planes to the given normalized device depth range.
+    ///
+    /// `fovy` is the vertical field of view in radians and `aspect` is the
+    /// ratio of width to height.
+    pub fn perspective_with(
+        fovy: f64,
+        aspect: f64,
+        near: f64,
+        far: f64,
+        range: DepthRange,
+    ) -> Self {
+        let m: [[f64; 4]; 4] = cgmath::perspective(cgmath::Rad(fovy), aspect, near, far).into();
+        let mut m = DMat4::from(m);
+        if range == DepthRange::ZeroToOne {
+            m.m22 = far / (near - far);
+            m.m32 = near * far / (near - far);
+        }
+        m
+    }
+
+    /// Right-handed perspective projection constructor for a frustum that is not
+    /// necessarily symmetric, such as for stereo rendering.
+    ///
+    /// `left`, `right`, `bottom`, and `top` are the bounds of the near clipping
+    /// plane at distance `near` from the eye. Following OpenGL conventions, the
+    /// near and far clipping planes map to -1 and 1 respectively in normalized
+    /// device coordinates.
+    pub fn frustum(left: f64, right: f64, bottom: f64, top: f64, near: f64, far: f64) -> Self {
+        let m: [[f64; 4]; 4] = cgmath::frustum(left, right, bottom, top, near, far).into();
+        DMat4::from(m)
+    }
+
+    /// Converts a projection matrix to or from reverse-Z by negating the depth
+    /// output, such that the near and far planes are swapped in normalized
+    /// device coordinates.
+    ///
+    /// Assumes the OpenGL depth range of `[-1, 1]`. Applying the conversion
+    /// twice yields the original matrix.
+    pub fn to_reverse_z(self) -> Self {
+        self.to_reverse_z_with(DepthRange::NegativeOneToOne)
+    }
+
+    /// Converts a projection matrix mapping to the given normalized device
+    /// depth range to or from reverse-Z, such that the near and far planes are
+    /// swapped.
+    ///
+    /// Depth `d` becomes `-d` for `[-1, 1]` and `1 - d` for `[0, 1]`. Applying
+    /// the conversion twice yields the original matrix.
+    pub fn to_reverse_z_with(self, range: DepthRange) -> Self {
+        let mut flip = DMat4::identity();
+        flip.m22 = -1.0;
+        if range == DepthRange::ZeroToOne {
+            flip.m32 = 1.0;
+        }
+        flip * self
+    }
+
+    /// Recovers the `(fovy, aspect, near, far)` parameters of a perspective
+    /// projection matrix.
+    ///
+    /// Assumes the OpenGL depth range of `[-1, 1]`; a matrix constructed with
+    /// `DepthRange::ZeroToOne` has the same form but yields wrong near and far
+    /// values, and must be passed to `perspective_params_with` instead.
+    /// Returns `None` if the matrix does not have the form of a matrix
+    /// constructed by `perspective`.
+    pub fn perspective_params(self) -> Option<(f64, f64, f64, f64)> {
+        self.perspective_params_with(DepthRange::NegativeOneToOne)
+    }
+
+    /// Recovers the `(fovy, aspect, near, far)` parameters of a perspective
+    /// projection matrix mapping to the given normalized device depth range.
+    ///
+    /// The depth range cannot be recovered from the matrix itself, since both
+    /// forms yield plausible parameters; it must match the range the matrix was
+    /// constructed with. Returns `None` if the matrix does not have the form of
+    /// a matrix constructed by `perspective_with`.
+    pub fn perspective_params_with(self, range: DepthRange) -> Option<(f64, f64, f64, f64)> {
+        let zeros = [
+            self.m01, self.m02, self.m03, self.m10, self.m12, self.m13, self.m20, self.m21,
+            self.m30, self.m31, self.m33,
+        ];
+        if zeros.iter().any(|&x| x != 0.0) || self.m23 != -1.0 {
+            return None;
+        }
+        let fovy = 2.0 * (1.0 / self.m11).atan();
+        let aspect = self.m11 / self.m00;
+        let near = match range {
+            DepthRange::NegativeOneToOne => self.m32 / (self.m22 - 1.0),
+            DepthRange::ZeroToOne => self.m32 / self.m22,
+        };
+        let far = self.m32 / (self.m22 + 1.0);
+        if self.m00 > 0.0 && self.m11 > 0.0 && near > 0.0 && far > near {
+            Some((fovy, aspect, near, far))
+        } else {
+            None
+        }
+    }
+
+    /// Left-handed view matrix constructor.
+    ///
+    /// The resulting matrix transforms `eye` to the origin and the direction
+    /// from `eye` towards `center` to the positive Z axis.
+    pub fn look_at_lh(eye: DVec3, center: DVec3, up: DVec3) -> Self {
+        let f = (center - eye).normalize();
+        let s = up.cross(f).normalize();
+        let u = f.cross(s);
+        DMat4::new(
+            s.x,
+            u.x,
+            f.x,
+            0.0,
+            s.y,
+            u.y,
+            f.y,
+            0.0,
+            s.z,
+            u.z,
+            f.z,
+            0.0,
+            -s.dot(eye),
+            -u.dot(eye),
+            -f.dot(eye),
+            1.0,
+        )
+    }
 }
 
 impl From<f32> for DMat4 {
@@ -786,6 +1874,19 @@ impl From<Mat4> for DMat4 {
 macro_rules! impl_matrix {
     ($self:ident, $minner:ty, $marray:ty, $vec:ty, $vinner:ty, $varray:ty, $base:ty) => {
         impl $self {
+            /// Zero matrix constructor.
+            pub fn zero() -> Self {
+                Self::diagonal(0.0)
+            }
+
+            /// Returns the product of the matrices in order, starting from the
+            /// identity, such that `concat([a, b, c])` is `a * b * c`.
+            ///
+            /// Returns the identity matrix if `matrices` is empty.
+            pub fn concat<I: IntoIterator<Item = $self>>(matrices: I) -> $self {
+                matrices.into_iter().fold(Self::identity(), |a, b| a * b)
+            }
+
             /// Computes the matrix determinant.
             pub fn determinant(self) -> $base {
                 use cgmath::SquareMatrix;
@@ -819,6 +1920,38 @@ macro_rules! impl_matrix {
                 m.into()
             }
 
+            /// Transposes the matrix in place.
+            pub fn transpose_mut(&mut self) {
+                let m: &mut $marray = self.as_mut();
+                for c in 0..m.len() {
+                    for r in (c + 1)..m.len() {
+                        let x = m[c][r];
+                        m[c][r] = m[r][c];
+                        m[r][c] = x;
+                    }
+                }
+            }
+
+            /// Returns `true` if the matrix determinant is non-zero relative to the
+            /// magnitude of the elements, i.e. if `|det| > ε · max_norm()ⁿ` for
+            /// an `n`x`n` matrix, so that the result does not depend on the
+            /// scale of the matrix.
+            pub fn is_invertible(self) -> bool {
+                let m: &$marray = self.as_ref();
+                let scale = self.max_norm().powi(m.len() as i32);
+                self.determinant().abs() > <$base>::EPSILON * scale
+            }
+
+            /// Returns `true` if the matrix is approximately equal to the identity
+            /// matrix.
+            pub fn is_identity(self) -> bool {
+                self.relative_eq(
+                    &Self::identity(),
+                    <Self as ApproxEq>::default_epsilon(),
+                    Self::default_max_relative(),
+                )
+            }
+
             /// Attempts to compute the matrix inverse, returning `None` if the matrix is
             /// non-invertible (i.e. has zero determinant).
             pub fn try_invert(self) -> Option<$self> {
@@ -829,6 +1962,123 @@ macro_rules! impl_matrix {
                     b.into()
                 })
             }
+
+            /// Returns the `i`-th row of the matrix.
+            ///
+            /// ## Panics
+            ///
+            /// Panics if `i` is out of range.
+            pub fn row(self, i: usize) -> $vec {
+                let mut v: $varray = Default::default();
+                for (j, x) in v.iter_mut().enumerate() {
+                    *x = self[(i, j)];
+                }
+                v.into()
+            }
+
+            /// Returns the `i`-th column of the matrix.
+            ///
+            /// ## Panics
+            ///
+            /// Panics if `i` is out of range.
+            pub fn column(self, i: usize) -> $vec {
+                let m: &$marray = self.as_ref();
+                m[i].into()
+            }
+
+            /// Sets the `i`-th row of the matrix.
+            ///
+            /// Since storage is column-major, the row elements are not contiguous
+            /// in memory: the `j`-th element of the row is written to the `i`-th
+            /// element of the `j`-th column.
+            ///
+            /// ## Panics
+            ///
+            /// Panics if `i` is out of range.
+            pub fn set_row(&mut self, i: usize, v: $vec) {
+                let v: $varray = v.into();
+                for (j, x) in v.iter().enumerate() {
+                    self[(i, j)] = *x;
+                }
+            }
+
+            /// Sets the `i`-th column of the matrix.
+            ///
+            /// Since storage is column-major, the column elements are contiguous
+            /// in memory.
+            ///
+            /// ## Panics
+            ///
+            /// Panics if `i` is out of range.
+            pub fn set_column(&mut self, i: usize, v: $vec) {
+                let v: $varray = v.into();
+                for (j, x) in v.iter().enumerate() {
+                    self[(j, i)] = *x;
+                }
+            }
+
+            /// Returns the element-wise linear interpolation between two matrices.
+            ///
+            /// This is a naive blend of the matrix elements and generally does not
+            /// produce a meaningful interpolation of the transforms they represent.
+            /// Consider using `Trs::lerp` to interpolate transforms.
+            pub fn lerp(self, other: $self, t: $base) -> $self {
+                self * (1.0 - t) + other * t
+            }
+
+            /// Returns the symmetric part of the matrix, `(M + Mᵀ) / 2`.
+            pub fn symmetric_part(self) -> $self {
+                (self + self.transpose()) * 0.5
+            }
+
+            /// Returns the skew-symmetric part of the matrix, `(M - Mᵀ) / 2`.
+            ///
+            /// The symmetric and skew-symmetric parts sum to the original matrix.
+            pub fn skew_part(self) -> $self {
+                (self - self.transpose()) * 0.5
+            }
+
+            /// Returns the Frobenius norm, i.e. the square root of the sum of the
+            /// squared elements.
+            pub fn frobenius_norm(self) -> $base {
+                let m: $marray = self.into();
+                m.iter().flatten().map(|x| x * x).sum::<$base>().sqrt()
+            }
+
+            /// Returns the max norm, i.e. the largest absolute value of any
+            /// element, or NaN if any element is NaN.
+            pub fn max_norm(self) -> $base {
+                let m: $marray = self.into();
+                m.iter().flatten().map(|x| x.abs()).fold(0.0, |max, x| {
+                    if x > max || x.is_nan() {
+                        x
+                    } else {
+                        max
+                    }
+                })
+            }
+
+            /// Constructs a matrix from a row-major array, where `m[r][c]` is the
+            /// element at row `r` and column `c`.
+            pub fn from_row_major_array(m: $marray) -> $self {
+                $self::from(m).transpose()
+            }
+
+            /// Returns the matrix as a row-major array, where `a[r][c]` is the
+            /// element at row `r` and column `c`.
+            ///
+            /// This is the transpose of the native column-major layout exposed by
+            /// `as_ref` and the `Into` conversion, for APIs that expect row-major
+            /// data.
+            pub fn to_row_major_array(self) -> $marray {
+                self.transpose().into()
+            }
+
+            /// Computes the matrix rank by Gaussian elimination, treating pivots
+            /// with magnitude less than or equal to `epsilon` as zero.
+            pub fn rank(self, epsilon: $base) -> usize {
+                scalar::rank(self.into(), epsilon)
+            }
         }
 
         impl ops::Add<$self> for $self {
@@ -841,6 +2091,25 @@ macro_rules! impl_matrix {
             }
         }
 
+        impl ops::AddAssign<$self> for $self {
+            fn add_assign(&mut self, rhs: $self) {
+                *self = *self + rhs;
+            }
+        }
+
+        /// Element-wise sum, starting from the zero matrix.
+        impl iter::Sum<$self> for $self {
+            fn sum<I: Iterator<Item = $self>>(iter: I) -> Self {
+                iter.fold(Self::zero(), |a, b| a + b)
+            }
+        }
+
+        impl<'a> iter::Sum<&'a $self> for $self {
+            fn sum<I: Iterator<Item = &'a $self>>(iter: I) -> Self {
+                iter.fold(Self::zero(), |a, b| a + *b)
+            }
+        }
+
         impl ops::Sub<$self> for $self {
             type Output = $self;
             fn sub(self, rhs: $self) -> Self::Output {
@@ -851,6 +2120,21 @@ macro_rules! impl_matrix {
             }
         }
 
+        impl ops::SubAssign<$self> for $self {
+            fn sub_assign(&mut self, rhs: $self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl ops::Neg for $self {
+            type Output = $self;
+            fn neg(self) -> Self::Output {
+                let a: &$minner = self.as_ref().into();
+                let m: $marray = (-*a).into();
+                m.into()
+            }
+        }
+
         impl ops::Mul<$base> for $self {
             type Output = $self;
             fn mul(self, rhs: $base) -> Self::Output {
@@ -860,6 +2144,12 @@ macro_rules! impl_matrix {
             }
         }
 
+        impl ops::MulAssign<$base> for $self {
+            fn mul_assign(&mut self, rhs: $base) {
+                *self = *self * rhs;
+            }
+        }
+
         impl ops::Mul<$vec> for $self {
             type Output = $vec;
             fn mul(self, rhs: $vec) -> Self::Output {
@@ -897,27 +2187,99 @@ macro_rules! impl_matrix {
             }
         }
 
+        impl<'a, 'b> ops::Mul<&'b $self> for &'a $self {
+            type Output = $self;
+            fn mul(self, rhs: &'b $self) -> Self::Output {
+                let a: &$minner = self.as_ref().into();
+                let b: &$minner = rhs.as_ref().into();
+                let v: $marray = (a * b).into();
+                v.into()
+            }
+        }
+
+        impl<'a> ops::Mul<$self> for &'a $self {
+            type Output = $self;
+            fn mul(self, rhs: $self) -> Self::Output {
+                self * &rhs
+            }
+        }
+
+        impl<'b> ops::Mul<&'b $self> for $self {
+            type Output = $self;
+            fn mul(self, rhs: &'b $self) -> Self::Output {
+                &self * rhs
+            }
+        }
+
+        impl ops::MulAssign<$self> for $self {
+            fn mul_assign(&mut self, rhs: $self) {
+                *self = *self * rhs;
+            }
+        }
+
+        /// Element access by `(row, column)` index.
+        ///
+        /// Note that since storage is column-major, `m[(row, col)]` refers to
+        /// the field `m.m{col}{row}`, e.g. `m[(1, 0)]` is `m.m01`.
+        ///
+        /// ## Panics
+        ///
+        /// Panics if either index is out of range.
+        impl ops::Index<(usize, usize)> for $self {
+            type Output = $base;
+            fn index(&self, (row, col): (usize, usize)) -> &$base {
+                let m: &$marray = self.as_ref();
+                &m[col][row]
+            }
+        }
+
+        /// Mutable element access by `(row, column)` index.
+        ///
+        /// ## Panics
+        ///
+        /// Panics if either index is out of range.
+        impl ops::IndexMut<(usize, usize)> for $self {
+            fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut $base {
+                let m: &mut $marray = self.as_mut();
+                &mut m[col][row]
+            }
+        }
+
         impl Default for $self {
             fn default() -> Self {
                 Self::identity()
             }
         }
 
+        // The matrix is `#[repr(C)]` with its elements in column-major order,
+        // hence it can be viewed as an array of columns provided the size and
+        // alignment agree.
+        const _: () = assert!(
+            mem::size_of::<$self>() == mem::size_of::<$marray>()
+                && mem::align_of::<$self>() == mem::align_of::<$marray>()
+        );
+
         impl AsRef<$marray> for $self {
             fn as_ref(&self) -> &$marray {
-                unsafe { mem::transmute(self) }
+                unsafe { &*(self as *const $self as *const $marray) }
+            }
+        }
+
+        impl AsMut<$marray> for $self {
+            fn as_mut(&mut self) -> &mut $marray {
+                unsafe { &mut *(self as *mut $self as *mut $marray) }
             }
         }
 
         impl From<$marray> for $self {
             fn from(array: $marray) -> Self {
-                unsafe { mem::transmute(array) }
+                unsafe { *(&array as *const $marray as *const $self) }
             }
         }
 
         impl Into<$marray> for $self {
             fn into(self) -> $marray {
-                unsafe { mem::transmute(self) }
+                *self.as_ref()
             }
         }
 
@@ -953,6 +2315,23 @@ macro_rules! impl_matrix {
                 a.ulps_eq(&b, epsilon, max_ulps)
             }
         }
+
+        impl AbsDiffEq for $self {
+            type Epsilon = $base;
+
+            fn default_epsilon() -> Self::Epsilon {
+                <$base>::EPSILON
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                let a: &$marray = self.as_ref();
+                let b: &$marray = other.as_ref();
+                a.iter()
+                    .flatten()
+                    .zip(b.iter().flatten())
+                    .all(|(x, y)| x.abs_diff_eq(y, epsilon))
+            }
+        }
     };
 }
 
@@ -1012,6 +2391,1005 @@ impl_matrix!(
     f64
 );
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn mat_rank() {
+        assert_eq!(Mat2::identity().rank(1.0e-6), 2);
+        assert_eq!(Mat3::identity().rank(1.0e-6), 3);
+        assert_eq!(DMat4::identity().rank(1.0e-12), 4);
+    }
+
+    #[test]
+    pub fn mat_rank_duplicated_row() {
+        #[rustfmt::skip]
+        let m = mat3!(
+            1.0, 2.0, 1.0,
+            4.0, 5.0, 4.0,
+            7.0, 8.0, 7.0,
+        );
+        assert_eq!(m.rank(1.0e-6), 2);
+        assert_eq!(DMat3::from(m).rank(1.0e-12), 2);
+        assert_eq!(Mat2::diagonal(0.0).rank(1.0e-6), 0);
+    }
+
+    #[test]
+    pub fn mat_row_major_array() {
+        let a = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+        assert_eq!(Mat3::from_row_major_array(a).to_row_major_array(), a);
+        assert_eq!(Mat3::from_row_major_array(a), Mat3::from(a).transpose());
+        assert_eq!(Mat3::from_row_major_array(a).m01, 4.0);
+
+        let a = [[1.0, 2.0], [3.0, 4.0]];
+        assert_eq!(DMat2::from_row_major_array(a).to_row_major_array(), a);
+        assert_eq!(DMat2::from_row_major_array(a), DMat2::from(a).transpose());
+
+        let m = Mat4::from_axis_angle(vec3!(1.0, 2.0, 3.0), 0.7) * Mat4::diagonal(2.0);
+        assert_eq!(&m.to_row_major_array(), m.transpose().as_ref());
+        assert_eq!(m.to_row_major_array()[0][3], m.m30);
+        let m = DMat4::from(m);
+        assert_eq!(&m.to_row_major_array(), m.transpose().as_ref());
+    }
+
+    #[test]
+    pub fn mat_index() {
+        #[rustfmt::skip]
+        let mut m = mat3!(
+            0.0, 1.0, 2.0,
+            3.0, 4.0, 5.0,
+            6.0, 7.0, 8.0,
+        );
+        assert_eq!(m[(0, 0)], m.m00);
+        assert_eq!(m[(1, 0)], m.m01);
+        assert_eq!(m[(0, 1)], m.m10);
+        assert_eq!(m[(2, 1)], m.m12);
+        assert_eq!(m[(1, 2)], m.m21);
+        m[(2, 0)] = 10.0;
+        assert_eq!(m.m02, 10.0);
+
+        let mut m = DMat4::identity();
+        m[(0, 3)] = 5.0;
+        assert_eq!(m.m30, 5.0);
+        assert_eq!(m[(3, 3)], 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn mat_index_out_of_range() {
+        let m = Mat2::identity();
+        let _ = m[(2, 0)];
+    }
+
+    #[test]
+    pub fn mat_lerp() {
+        #[rustfmt::skip]
+        let a = mat2!(
+            1.0, 2.0,
+            3.0, 4.0,
+        );
+        let b = Mat2::diagonal(3.0);
+        #[rustfmt::skip]
+        let mid = mat2!(
+            2.0, 1.0,
+            1.5, 3.5,
+        );
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.5), mid);
+        assert_eq!(DMat2::from(a).lerp(DMat2::from(b), 0.5), DMat2::from(mid));
+    }
+
+    #[test]
+    pub fn mat_assign_ops() {
+        let a = Mat4::from_angle_x(0.5) * 2.0;
+        let b = Mat4::from_axis_angle(vec3!(1.0, 2.0, 3.0), 1.5);
+
+        let mut m = a;
+        m *= b;
+        assert_eq!(m, a * b);
+
+        let mut m = a;
+        m += b;
+        assert_eq!(m, a + b);
+
+        let mut m = a;
+        m -= b;
+        assert_eq!(m, a - b);
+
+        let mut m = a;
+        m *= 3.0;
+        assert_eq!(m, a * 3.0);
+
+        let mut m = DMat4::from(a);
+        m *= DMat4::from(b);
+        assert_eq!(m, DMat4::from(a) * DMat4::from(b));
+    }
+
+    #[test]
+    pub fn mat_is_invertible_is_identity() {
+        assert!(Mat3::identity().is_invertible());
+        assert!(Mat3::identity().is_identity());
+        assert!(DMat4::identity().is_invertible());
+        assert!(DMat4::identity().is_identity());
+
+        #[rustfmt::skip]
+        let singular = mat2!(
+            1.0, 2.0,
+            2.0, 4.0,
+        );
+        assert!(!singular.is_invertible());
+        assert!(!singular.is_identity());
+        assert!(!Mat4::diagonal(0.0).is_invertible());
+        assert!(Mat4::diagonal(0.01).is_invertible());
+        assert!(DMat3::diagonal(1.0e-6).is_invertible());
+        assert!(!(singular * 1.0e6).is_invertible());
+
+        let rotation = Mat3::from_angle_z(1.0);
+        assert!(rotation.is_invertible());
+        assert!(!rotation.is_identity());
+        assert!((rotation * rotation.inverse()).is_identity());
+    }
+
+    #[test]
+    pub fn mat_cols() {
+        assert_eq!(
+            Mat2::from_cols_array(Mat2::identity().to_cols_array()),
+            Mat2::identity()
+        );
+        assert_eq!(
+            DMat3::from_cols_array(DMat3::identity().to_cols_array()),
+            DMat3::identity()
+        );
+        assert_eq!(
+            Mat4::from_cols(
+                vec4!(1.0, 0.0, 0.0, 0.0),
+                vec4!(0.0, 1.0, 0.0, 0.0),
+                vec4!(0.0, 0.0, 1.0, 0.0),
+                vec4!(0.0, 0.0, 0.0, 1.0),
+            ),
+            Mat4::identity()
+        );
+
+        #[rustfmt::skip]
+        let m = mat3!(
+            1.0, 2.0, 3.0,
+            4.0, 5.0, 6.0,
+            7.0, 8.0, 9.0,
+        );
+        let flat = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+        assert_eq!(m.to_cols_array(), flat);
+        assert_eq!(Mat3::from_cols_array(flat), m);
+        assert_eq!(
+            Mat3::from_cols(
+                vec3!(1.0, 2.0, 3.0),
+                vec3!(4.0, 5.0, 6.0),
+                vec3!(7.0, 8.0, 9.0),
+            ),
+            m
+        );
+
+        let m = DMat4::from(Mat4::from_angle_x(1.0));
+        assert_eq!(DMat4::from_cols_array(m.to_cols_array()), m);
+        assert_eq!(DMat2::from_cols(dvec2!(1, 2), dvec2!(3, 4)).m10, 3.0);
+    }
+
+    #[test]
+    pub fn mat_neg() {
+        assert_eq!(-Mat2::identity(), Mat2::diagonal(-1.0));
+        assert_eq!(-DMat3::identity(), DMat3::diagonal(-1.0));
+        let m = Mat4::from_angle_z(0.5);
+        assert_eq!(m + -m, Mat4::diagonal(0.0));
+        assert_eq!(-(-m), m);
+    }
+
+    #[test]
+    pub fn mat_rows_columns() {
+        let mut m = Mat3::identity();
+        m.set_row(0, vec3!(1.0, 2.0, 3.0));
+        assert_eq!(m.row(0), vec3!(1.0, 2.0, 3.0));
+        assert_eq!(m.column(0), vec3!(1.0, 0.0, 0.0));
+        assert_eq!(
+            m.as_ref(),
+            &[[1.0, 0.0, 0.0], [2.0, 1.0, 0.0], [3.0, 0.0, 1.0]]
+        );
+
+        m.set_column(2, vec3!(4.0, 5.0, 6.0));
+        assert_eq!(m.column(2), vec3!(4.0, 5.0, 6.0));
+        assert_eq!(m.row(1), vec3!(0.0, 1.0, 5.0));
+        assert_eq!(
+            m.as_ref(),
+            &[[1.0, 0.0, 0.0], [2.0, 1.0, 0.0], [4.0, 5.0, 6.0]]
+        );
+
+        let mut m = DMat4::identity();
+        m.set_row(3, dvec4!(1, 2, 3, 4));
+        assert_eq!(m.row(3), dvec4!(1, 2, 3, 4));
+        assert_eq!((m.m03, m.m13, m.m23, m.m33), (1.0, 2.0, 3.0, 4.0));
+    }
+
+    #[test]
+    pub fn mat_rotation() {
+        use std::f32::consts::PI;
+        let x = vec3!(1.0, 0.0, 0.0);
+        approx::assert_relative_eq!(Mat3::from_angle_z(PI / 2.0) * x, vec3!(0.0, 1.0, 0.0));
+        approx::assert_relative_eq!(Mat3::from_angle_y(PI / 2.0) * x, vec3!(0.0, 0.0, -1.0));
+        approx::assert_relative_eq!(
+            Mat3::from_angle_x(PI / 2.0) * vec3!(0.0, 1.0, 0.0),
+            vec3!(0.0, 0.0, 1.0)
+        );
+        approx::assert_relative_eq!(
+            Mat3::from_axis_angle(vec3!(0.0, 0.0, 2.0), PI / 2.0),
+            Mat3::from_angle_z(PI / 2.0)
+        );
+        approx::assert_relative_eq!(
+            Mat4::from_angle_z(PI / 2.0) * vec4!(x, 1.0),
+            vec4!(0.0, 1.0, 0.0, 1.0)
+        );
+        approx::assert_relative_eq!(
+            Mat4::from_axis_angle(vec3!(1.0, 1.0, 0.0), 1.0),
+            Mat4::from(Mat3::from_axis_angle(vec3!(1.0, 1.0, 0.0), 1.0))
+        );
+
+        use std::f64::consts::PI as DPI;
+        approx::assert_relative_eq!(
+            DMat3::from_angle_z(DPI / 2.0) * dvec3!(1.0, 0.0, 0.0),
+            dvec3!(0.0, 1.0, 0.0)
+        );
+        approx::assert_relative_eq!(
+            DMat4::from_axis_angle(dvec3!(0.0, 0.0, 1.0), DPI / 2.0),
+            DMat4::from_angle_z(DPI / 2.0)
+        );
+    }
+
+    #[test]
+    pub fn mat4_perspective_params() {
+        let (fovy, aspect, near, far) = (1.2, 16.0 / 9.0, 0.1, 100.0);
+        let (a, b, c, d) = Mat4::perspective(fovy, aspect, near, far)
+            .perspective_params()
+            .unwrap();
+        approx::assert_relative_eq!(a, fovy, epsilon = 1.0e-5);
+        approx::assert_relative_eq!(b, aspect, epsilon = 1.0e-5);
+        approx::assert_relative_eq!(c, near, epsilon = 1.0e-5);
+        approx::assert_relative_eq!(d, far, max_relative = 1.0e-3);
+
+        let (fovy, aspect, near, far) = (1.2, 16.0 / 9.0, 0.1, 100.0);
+        let (a, b, c, d) = DMat4::perspective(fovy, aspect, near, far)
+            .perspective_params()
+            .unwrap();
+        approx::assert_relative_eq!(a, fovy, epsilon = 1.0e-12);
+        approx::assert_relative_eq!(b, aspect, epsilon = 1.0e-12);
+        approx::assert_relative_eq!(c, near, epsilon = 1.0e-12);
+        approx::assert_relative_eq!(d, far, max_relative = 1.0e-9);
+
+        assert_eq!(Mat4::identity().perspective_params(), None);
+        assert_eq!(
+            Mat4::look_at(vec3!(), vec3!(0.0, 0.0, -1.0), vec3!(0.0, 1.0, 0.0))
+                .perspective_params(),
+            None
+        );
+    }
+
+    #[test]
+    pub fn mat4_transform_point_vector() {
+        let mut translation = Mat4::identity();
+        translation.m30 = 1.0;
+        translation.m31 = 2.0;
+        translation.m32 = 3.0;
+        let p = vec3!(1.0, -1.0, 0.5);
+        assert_eq!(translation.transform_point(p), vec3!(2.0, 1.0, 3.5));
+        assert_eq!(translation.transform_vector(p), p);
+
+        let projection = Mat4::perspective(1.0, 1.0, 1.0, 10.0);
+        let p = projection.transform_point(vec3!(0.5, 0.5, -2.0));
+        let clip = projection * vec4!(0.5, 0.5, -2.0, 1.0);
+        approx::assert_relative_eq!(p, clip.xyz() / 2.0);
+        approx::assert_relative_eq!(
+            projection.transform_point(vec3!(0.0, 0.0, -1.0)),
+            vec3!(0.0, 0.0, -1.0)
+        );
+
+        let projection = DMat4::from(projection);
+        approx::assert_relative_eq!(
+            projection.transform_point(dvec3!(0.0, 0.0, -10.0)),
+            dvec3!(0.0, 0.0, 1.0),
+            epsilon = 1.0e-6
+        );
+    }
+
+    #[test]
+    pub fn mat4_normal_matrix() {
+        let model = crate::Trs::new(
+            vec3!(1.0, 2.0, 3.0),
+            quat!(0.0, 1.0, 0.0; 0.5),
+            vec3!(4.0, 0.5, 1.0),
+        )
+        .matrix();
+        let normal = vec3!(1.0, 1.0, 0.0);
+        let tangents = [vec3!(1.0, -1.0, 0.0), vec3!(0.0, 0.0, 1.0)];
+        let n = model.normal_matrix() * normal;
+        for &t in tangents.iter() {
+            let t = model.transform_vector(t);
+            approx::assert_relative_eq!(n.dot(t), 0.0, epsilon = 1.0e-6);
+        }
+        let naive = model.transform_vector(normal);
+        assert!(naive.dot(model.transform_vector(tangents[0])).abs() > 0.1);
+
+        assert!(DMat4::diagonal(0.0).try_normal_matrix().is_none());
+        approx::assert_relative_eq!(
+            DMat4::from(model).normal_matrix(),
+            DMat3::from(model.normal_matrix()),
+            epsilon = 1.0e-6
+        );
+    }
+
+    #[test]
+    pub fn mat4_to_reverse_z() {
+        let projection = Mat4::perspective(1.0, 1.5, 0.5, 50.0);
+        let reverse_z = projection.to_reverse_z();
+        let near = vec3!(0.0, 0.0, -0.5);
+        let far = vec3!(0.0, 0.0, -50.0);
+        approx::assert_relative_eq!(projection.transform_point(near).z, -1.0);
+        approx::assert_relative_eq!(reverse_z.transform_point(near).z, 1.0);
+        approx::assert_relative_eq!(reverse_z.transform_point(far).z, -1.0, epsilon = 1.0e-6);
+        assert_eq!(reverse_z.to_reverse_z(), projection);
+
+        let projection = DMat4::perspective(1.0, 1.5, 0.5, 50.0);
+        assert_eq!(projection.to_reverse_z().to_reverse_z(), projection);
+        assert_eq!(
+            projection.to_reverse_z_with(DepthRange::NegativeOneToOne),
+            projection.to_reverse_z()
+        );
+
+        let range = DepthRange::ZeroToOne;
+        let projection = Mat4::perspective_with(1.0, 1.5, 0.5, 50.0, range);
+        let reverse_z = projection.to_reverse_z_with(range);
+        approx::assert_relative_eq!(projection.transform_point(near).z, 0.0, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(reverse_z.transform_point(near).z, 1.0, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(reverse_z.transform_point(far).z, 0.0, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(
+            reverse_z.to_reverse_z_with(range),
+            projection,
+            epsilon = 1.0e-6
+        );
+
+        let projection = DMat4::perspective_with(1.0, 1.5, 0.5, 50.0, range);
+        let reverse_z = projection.to_reverse_z_with(range);
+        approx::assert_relative_eq!(
+            reverse_z.transform_point(dvec3!(0.0, 0.0, -0.5)).z,
+            1.0,
+            epsilon = 1.0e-12
+        );
+        approx::assert_relative_eq!(
+            reverse_z.to_reverse_z_with(range),
+            projection,
+            epsilon = 1.0e-12
+        );
+    }
+
+    #[test]
+    pub fn mat4_look_at() {
+        let eye = vec3!(1.0, 2.0, 3.0);
+        let center = vec3!(4.0, -2.0, 3.0);
+        let up = vec3!(0.0, 1.0, 0.0);
+
+        let view = Mat4::look_at(eye, center, up);
+        approx::assert_relative_eq!(view * vec4!(eye, 1.0), vec4!(0.0, 0.0, 0.0, 1.0));
+        approx::assert_relative_eq!(
+            view * vec4!(center, 1.0),
+            vec4!(0.0, 0.0, -5.0, 1.0),
+            epsilon = 1.0e-5
+        );
+
+        let view = Mat4::look_at_lh(eye, center, up);
+        approx::assert_relative_eq!(view * vec4!(eye, 1.0), vec4!(0.0, 0.0, 0.0, 1.0));
+        approx::assert_relative_eq!(
+            view * vec4!(center, 1.0),
+            vec4!(0.0, 0.0, 5.0, 1.0),
+            epsilon = 1.0e-5
+        );
+
+        let view = DMat4::look_at(dvec3!(eye), dvec3!(center), dvec3!(up));
+        approx::assert_relative_eq!(
+            view * dvec4!(dvec3!(center), 1.0),
+            dvec4!(0.0, 0.0, -5.0, 1.0),
+            epsilon = 1.0e-12
+        );
+    }
+
+    #[test]
+    pub fn mat_abs_diff_eq() {
+        let a = Mat3::from_angle_z(0.5);
+        let mut b = a;
+        b[(1, 2)] += 1.0e-7;
+        approx05::assert_abs_diff_eq!(a, b, epsilon = 1.0e-6);
+        b[(2, 0)] += 1.0e-3;
+        approx05::assert_abs_diff_ne!(a, b, epsilon = 1.0e-6);
+        approx05::assert_abs_diff_eq!(DMat4::identity(), DMat4::identity());
+    }
+
+    #[test]
+    pub fn mat3_affine2() {
+        use std::f32::consts::FRAC_PI_2;
+        let m = Mat3::from_translation2(Vec2::new(1.0, 2.0)) * Mat3::from_angle(FRAC_PI_2);
+        approx::assert_relative_eq!(
+            m.transform_point2(Vec2::new(3.0, 0.0)),
+            Vec2::new(1.0, 5.0),
+            epsilon = 1.0e-6
+        );
+        approx::assert_relative_eq!(
+            m.transform_vector2(Vec2::new(3.0, 0.0)),
+            Vec2::new(0.0, 3.0),
+            epsilon = 1.0e-6
+        );
+        let m =
+            Mat3::from_scale2(Vec2::new(2.0, -1.0)) * Mat3::from_translation2(Vec2::new(1.0, 1.0));
+        assert_eq!(
+            m.transform_point2(Vec2::new(1.0, 2.0)),
+            Vec2::new(4.0, -3.0)
+        );
+        assert_eq!(
+            m.transform_vector2(Vec2::new(1.0, 2.0)),
+            Vec2::new(2.0, -2.0)
+        );
+
+        let m = DMat3::from_translation2(DVec2::new(1.0, 2.0))
+            * DMat3::from_angle(std::f64::consts::FRAC_PI_2);
+        approx::assert_relative_eq!(
+            m.transform_point2(DVec2::new(3.0, 0.0)),
+            DVec2::new(1.0, 5.0),
+            epsilon = 1.0e-12
+        );
+    }
+
+    #[test]
+    pub fn mat_symmetric_skew_parts() {
+        #[rustfmt::skip]
+        let m = Mat3::new(
+            1.0, 2.0, 3.0,
+            4.0, 5.0, 6.0,
+            7.0, 8.0, 10.0,
+        );
+        let sym = m.symmetric_part();
+        let skew = m.skew_part();
+        assert_eq!(sym + skew, m);
+        assert_eq!(sym, sym.transpose());
+        assert_eq!(skew, -skew.transpose());
+        assert_eq!(skew[(0, 0)], 0.0);
+        assert_eq!(sym[(1, 0)], 3.0);
+
+        let m = DMat4::from_cols_array([
+            1.0, -2.0, 3.0, 4.0, 5.0, 6.0, -7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+        ]);
+        assert_eq!(m.symmetric_part() + m.skew_part(), m);
+        assert_eq!(m.skew_part(), -m.skew_part().transpose());
+    }
+
+    #[test]
+    pub fn mat4_transform_points() {
+        let m = Mat4::perspective(1.0, 1.5, 0.1, 100.0) * Mat4::from_angle_y(0.3);
+        let points = [
+            vec3!(1.0, 2.0, -3.0),
+            vec3!(-4.0, 0.5, -10.0),
+            vec3!(0.0, 0.0, -1.0),
+        ];
+        let mut out = [Vec3::default(); 3];
+        m.transform_points(&points, &mut out);
+        for (p, o) in points.iter().zip(out.iter()) {
+            assert_eq!(m.transform_point(*p), *o);
+        }
+
+        let m = DMat4::from_angle_x(0.5);
+        let points = [dvec3!(1.0, 2.0, 3.0), dvec3!(-1.0, 0.0, 7.0)];
+        let mut out = [DVec3::default(); 2];
+        m.transform_points(&points, &mut out);
+        assert_eq!(m.transform_point(points[1]), out[1]);
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn mat4_transform_points_length_mismatch() {
+        let mut out = [Vec3::default(); 1];
+        Mat4::identity().transform_points(&[Vec3::default(); 2], &mut out);
+    }
+
+    #[test]
+    pub fn mat4_frustum() {
+        let (fovy, aspect, near, far) = (1.2f32, 1.6, 0.5, 50.0);
+        let top = near * (0.5 * fovy).tan();
+        let right = top * aspect;
+        approx::assert_relative_eq!(
+            Mat4::frustum(-right, right, -top, top, near, far),
+            Mat4::perspective(fovy, aspect, near, far),
+            epsilon = 1.0e-6
+        );
+
+        let m = Mat4::frustum(-0.3, 0.1, -0.2, 0.4, near, far);
+        let corners = [
+            (vec3!(-0.3, -0.2, -near), vec3!(-1.0, -1.0, -1.0)),
+            (vec3!(0.1, -0.2, -near), vec3!(1.0, -1.0, -1.0)),
+            (vec3!(-0.3, 0.4, -near), vec3!(-1.0, 1.0, -1.0)),
+            (vec3!(0.1, 0.4, -near), vec3!(1.0, 1.0, -1.0)),
+        ];
+        for &(p, ndc) in corners.iter() {
+            approx::assert_relative_eq!(m.transform_point(p), ndc, epsilon = 1.0e-5);
+        }
+        let far_corner = vec3!(0.1, 0.4, -near) * (far / near);
+        approx::assert_relative_eq!(
+            m.transform_point(far_corner),
+            vec3!(1.0, 1.0, 1.0),
+            epsilon = 1.0e-5
+        );
+
+        let m = DMat4::frustum(-1.0, 2.0, -1.0, 0.5, 1.0, 10.0);
+        approx::assert_relative_eq!(
+            m.transform_point(dvec3!(2.0, 0.5, -1.0)),
+            dvec3!(1.0, 1.0, -1.0),
+            epsilon = 1.0e-12
+        );
+    }
+
+    #[test]
+    pub fn mat_orthonormalize() {
+        let mut m = Mat3::from_axis_angle(vec3!(1.0, 2.0, 3.0), 0.8);
+        m[(0, 1)] += 0.01;
+        m[(2, 0)] -= 0.02;
+        m[(1, 2)] += 0.005;
+        let r = m.orthonormalize();
+        for i in 0..3 {
+            approx::assert_relative_eq!(r.column(i).length(), 1.0, epsilon = 1.0e-6);
+            for j in (i + 1)..3 {
+                approx::assert_relative_eq!(r.column(i).dot(r.column(j)), 0.0, epsilon = 1.0e-6);
+            }
+        }
+        approx::assert_relative_eq!(r.column(0), m.column(0).normalize(), epsilon = 1.0e-6);
+        approx::assert_relative_eq!(r.determinant(), 1.0, epsilon = 1.0e-5);
+
+        let mut m = DMat4::from_axis_angle(dvec3!(0.0, 1.0, 1.0), -2.0);
+        m.m10 += 0.03;
+        m.m30 = 5.0;
+        let r = m.orthonormalize();
+        approx::assert_relative_eq!(
+            DMat3::from(r) * DMat3::from(r).transpose(),
+            DMat3::identity(),
+            epsilon = 1.0e-12
+        );
+        assert_eq!(r.m30, 5.0);
+        assert_eq!(r.m33, 1.0);
+    }
+
+    #[test]
+    pub fn mat_is_right_handed() {
+        let r = Mat3::from_axis_angle(vec3!(1.0, -1.0, 2.0), 2.5);
+        assert!(r.is_right_handed());
+        assert!(!(r * Mat3::from_scale2(Vec2::new(-1.0, 1.0))).is_right_handed());
+
+        let mut m = DMat4::from_angle_y(0.4);
+        m.m30 = -3.0;
+        assert!(m.is_right_handed());
+        m.m22 = -m.m22;
+        m.m02 = -m.m02;
+        assert!(!m.is_right_handed());
+        assert!(!DMat3::from(m).is_right_handed());
+        assert!(Mat4::identity().is_right_handed());
+    }
+
+    #[test]
+    pub fn mat_sum_concat() {
+        let a = Mat4::from_angle_x(0.3);
+        let b = Mat4::from_axis_angle(vec3!(1.0, 1.0, 0.0), -1.2);
+        let mut c = Mat4::from_angle_z(2.0);
+        c.m30 = 4.0;
+        let matrices = [a, b, c];
+        assert_eq!(matrices.iter().sum::<Mat4>(), a + b + c);
+        assert_eq!(matrices.iter().cloned().sum::<Mat4>(), a + b + c);
+        assert_eq!(Mat4::concat(matrices.iter().cloned()), a * b * c);
+        assert_eq!(Mat4::concat(Vec::new()), Mat4::identity());
+        assert_eq!(
+            Vec::<DMat2>::new().into_iter().sum::<DMat2>(),
+            DMat2::zero()
+        );
+        assert_eq!(Mat3::zero().as_ref(), &[[0.0; 3]; 3]);
+    }
+
+    #[test]
+    pub fn mat_array_view_field_order() {
+        let m = Mat2::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(m.as_ref(), &[[m.m00, m.m01], [m.m10, m.m11]]);
+        assert_eq!(m.as_ref(), &[[1.0, 2.0], [3.0, 4.0]]);
+        let m = DMat2::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(m.as_ref(), &[[1.0, 2.0], [3.0, 4.0]]);
+
+        let a = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0];
+        let m = Mat3::from_cols_array(a);
+        assert_eq!(
+            m.as_ref(),
+            &[
+                [m.m00, m.m01, m.m02],
+                [m.m10, m.m11, m.m12],
+                [m.m20, m.m21, m.m22]
+            ]
+        );
+        assert_eq!(
+            m.as_ref(),
+            &[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]
+        );
+        let m = DMat3::from_cols_array(a.map(f64::from));
+        assert_eq!(
+            m.as_ref(),
+            &[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]
+        );
+
+        #[rustfmt::skip]
+        let a = [
+            1.0, 2.0, 3.0, 4.0,
+            5.0, 6.0, 7.0, 8.0,
+            9.0, 10.0, 11.0, 12.0,
+            13.0, 14.0, 15.0, 16.0,
+        ];
+        let m = Mat4::from_cols_array(a);
+        assert_eq!(m.as_ref()[0], [m.m00, m.m01, m.m02, m.m03]);
+        assert_eq!(m.as_ref()[1], [m.m10, m.m11, m.m12, m.m13]);
+        assert_eq!(m.as_ref()[2], [m.m20, m.m21, m.m22, m.m23]);
+        assert_eq!(m.as_ref()[3], [m.m30, m.m31, m.m32, m.m33]);
+        assert_eq!(m.as_ref()[3], [13.0, 14.0, 15.0, 16.0]);
+        let m = DMat4::from_cols_array(a.map(f64::from));
+        assert_eq!(m.as_ref()[2], [9.0, 10.0, 11.0, 12.0]);
+        assert_eq!(DMat4::from(*m.as_ref()), m);
+        let b: [[f64; 4]; 4] = m.into();
+        assert_eq!(&b, m.as_ref());
+    }
+
+    #[test]
+    pub fn mat4_perspective_with() {
+        let (fovy, aspect, near, far) = (1.0f32, 1.5, 0.5, 20.0);
+        let gl = Mat4::perspective_with(fovy, aspect, near, far, DepthRange::NegativeOneToOne);
+        assert_eq!(gl, Mat4::perspective(fovy, aspect, near, far));
+        approx::assert_relative_eq!(
+            gl.transform_point(vec3!(0.0, 0.0, -near)).z,
+            -1.0,
+            epsilon = 1.0e-6
+        );
+        approx::assert_relative_eq!(
+            gl.transform_point(vec3!(0.0, 0.0, -far)).z,
+            1.0,
+            epsilon = 1.0e-5
+        );
+
+        let wgpu = Mat4::perspective_with(fovy, aspect, near, far, DepthRange::ZeroToOne);
+        approx::assert_relative_eq!(
+            wgpu.transform_point(vec3!(1.0, 0.0, -near)).z,
+            0.0,
+            epsilon = 1.0e-6
+        );
+        approx::assert_relative_eq!(
+            wgpu.transform_point(vec3!(0.0, 2.0, -far)).z,
+            1.0,
+            epsilon = 1.0e-5
+        );
+        assert_eq!(
+            wgpu.transform_point(vec3!(1.0, 2.0, -3.0)).xy(),
+            gl.transform_point(vec3!(1.0, 2.0, -3.0)).xy()
+        );
+
+        let m = DMat4::perspective_with(1.2, 1.0, 0.1, 1000.0, DepthRange::ZeroToOne);
+        approx::assert_relative_eq!(
+            m.transform_point(dvec3!(0.0, 0.0, -0.1)).z,
+            0.0,
+            epsilon = 1.0e-12
+        );
+        approx::assert_relative_eq!(
+            m.transform_point(dvec3!(0.0, 0.0, -1000.0)).z,
+            1.0,
+            epsilon = 1.0e-12
+        );
+        assert_eq!(DepthRange::default(), DepthRange::NegativeOneToOne);
+
+        let (a, b, c, d) = wgpu.perspective_params_with(DepthRange::ZeroToOne).unwrap();
+        approx::assert_relative_eq!(a, fovy, epsilon = 1.0e-5);
+        approx::assert_relative_eq!(b, aspect, epsilon = 1.0e-5);
+        approx::assert_relative_eq!(c, near, epsilon = 1.0e-5);
+        approx::assert_relative_eq!(d, far, max_relative = 1.0e-4);
+        assert_eq!(
+            gl.perspective_params_with(DepthRange::NegativeOneToOne),
+            gl.perspective_params()
+        );
+
+        let (fovy, aspect, near, far) = m.perspective_params_with(DepthRange::ZeroToOne).unwrap();
+        approx::assert_relative_eq!(fovy, 1.2, epsilon = 1.0e-12);
+        approx::assert_relative_eq!(aspect, 1.0, epsilon = 1.0e-12);
+        approx::assert_relative_eq!(near, 0.1, epsilon = 1.0e-12);
+        approx::assert_relative_eq!(far, 1000.0, max_relative = 1.0e-9);
+
+        let m = Mat4::perspective_with(1.0, 1.0, 1.0, 100.0, DepthRange::ZeroToOne);
+        let (_, _, near, far) = m.perspective_params_with(DepthRange::ZeroToOne).unwrap();
+        approx::assert_relative_eq!(near, 1.0, epsilon = 1.0e-5);
+        approx::assert_relative_eq!(far, 100.0, max_relative = 1.0e-5);
+    }
+
+    #[test]
+    pub fn mat_adjugate_cofactor() {
+        #[rustfmt::skip]
+        let m = Mat3::from_row_major_array([
+            [2.0, -1.0, 0.0],
+            [1.0, 3.0, 4.0],
+            [0.5, 0.0, -2.0],
+        ]);
+        assert_eq!(m.cofactor(0, 0), -6.0);
+        assert_eq!(m.cofactor(0, 1), 4.0);
+        assert_eq!(m.cofactor(1, 2), -0.5);
+        approx::assert_relative_eq!(
+            m * m.adjugate(),
+            Mat3::diagonal(m.determinant()),
+            epsilon = 1.0e-5
+        );
+        approx::assert_relative_eq!(
+            m.adjugate() * (1.0 / m.determinant()),
+            m.inverse(),
+            epsilon = 1.0e-6
+        );
+
+        let m = DMat3::from_angle_x(0.3) * DMat3::from_scale2(DVec2::new(2.0, 5.0));
+        approx::assert_relative_eq!(
+            m * m.adjugate(),
+            DMat3::diagonal(m.determinant()),
+            epsilon = 1.0e-12
+        );
+
+        let m = Mat2::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(m * m.adjugate(), Mat2::diagonal(m.determinant()));
+        assert_eq!(m.cofactor(0, 1), -m[(1, 0)]);
+        let m = DMat2::new(-1.5, 0.5, 2.0, 3.0);
+        assert_eq!(m.adjugate() * m, DMat2::diagonal(m.determinant()));
+        assert_eq!(m.cofactor(1, 1), m[(0, 0)]);
+    }
+
+    #[test]
+    pub fn mat_zero() {
+        assert_eq!(Mat2::zero().to_cols_array(), [0.0; 4]);
+        assert_eq!(Mat3::zero().to_cols_array(), [0.0; 9]);
+        assert_eq!(Mat4::zero().to_cols_array(), [0.0; 16]);
+        assert_eq!(DMat2::zero().to_cols_array(), [0.0; 4]);
+        assert_eq!(DMat3::zero().to_cols_array(), [0.0; 9]);
+        assert_eq!(DMat4::zero().to_cols_array(), [0.0; 16]);
+        assert_eq!(Mat3::zero() + Mat3::identity(), Mat3::identity());
+    }
+
+    #[test]
+    pub fn mat_display_precision() {
+        let m = Mat2::new(1.0, 2.25, -0.5, 4.0);
+        assert_eq!(format!("{}", m), "(1, 2.25; -0.5, 4)");
+        assert_eq!(format!("{:.1}", m), "(1.0, 2.2; -0.5, 4.0)");
+        assert_eq!(
+            format!("{:.1}", DMat3::identity()),
+            "(1.0, 0.0, 0.0; 0.0, 1.0, 0.0; 0.0, 0.0, 1.0)"
+        );
+    }
+
+    #[test]
+    pub fn mat4_decompose() {
+        use crate::{DTrs, Trs};
+        let trs = Trs::new(
+            vec3!(1.0, -2.0, 3.0),
+            quat!(1.0, 2.0, 3.0; 0.9),
+            vec3!(2.0, 0.5, 1.5),
+        );
+        let (t, r, s) = trs.matrix().decompose();
+        approx::assert_relative_eq!(Trs::new(t, r, s).matrix(), trs.matrix(), epsilon = 1.0e-5);
+
+        let mut m = DMat4::from_axis_angle(dvec3!(0.0, 1.0, 1.0), 2.0);
+        m.m00 = -m.m00;
+        m.m01 = -m.m01;
+        m.m02 = -m.m02;
+        m.m31 = 7.0;
+        let (t, r, s) = m.decompose();
+        assert!(s.x < 0.0);
+        assert_eq!(t, dvec3!(0.0, 7.0, 0.0));
+        approx::assert_relative_eq!(DTrs::new(t, r, s).matrix(), m, epsilon = 1.0e-12);
+
+        let m = trs.with_scale(vec3!(2.0, 0.0, 1.5)).matrix();
+        let (t, r, s) = m.decompose();
+        assert_eq!(r, quat!());
+        approx::assert_relative_eq!(t, trs.t);
+        approx::assert_relative_eq!(s, vec3!(2.0, 0.0, 1.5), epsilon = 1.0e-6);
+        let (_, r, s) = DMat4::diagonal(0.0).decompose();
+        assert_eq!(r, dquat!());
+        assert_eq!(s, dvec3!());
+    }
+
+    #[test]
+    pub fn mat_ref_mul() {
+        let a = Mat4::from_axis_angle(vec3!(1.0, 2.0, 3.0), 0.7) * Mat4::diagonal(2.0);
+        let b = Mat4::from_angle_x(-0.4);
+        let (ra, rb) = (&a, &b);
+        assert_eq!(ra * rb, a * b);
+        assert_eq!(ra * b, a * b);
+        assert_eq!(a * rb, a * b);
+
+        let c = DMat3::from_axis_angle(dvec3!(0.0, 1.0, 0.0), 1.2);
+        let d = DMat3::diagonal(3.0);
+        let (rc, rd) = (&c, &d);
+        assert_eq!(rc * rd, c * d);
+
+        let e = Mat2::from_cols(vec2!(1.0, 2.0), vec2!(3.0, 4.0));
+        let re = &e;
+        assert_eq!(re * re, e * e);
+    }
+
+    #[test]
+    pub fn mat4_reflection() {
+        // The plane y = 2.
+        let m = Mat4::reflection(vec3!(0.0, 1.0, 0.0), -2.0);
+        assert_eq!(
+            m.transform_point(vec3!(5.0, 2.0, -1.0)),
+            vec3!(5.0, 2.0, -1.0)
+        );
+        assert_eq!(
+            m.transform_point(vec3!(5.0, 3.0, -1.0)),
+            vec3!(5.0, 1.0, -1.0)
+        );
+        assert_eq!(
+            m.transform_vector(vec3!(1.0, 1.0, 1.0)),
+            vec3!(1.0, -1.0, 1.0)
+        );
+        assert_eq!(m * m, Mat4::identity());
+
+        let n = dvec3!(1.0, -2.0, 2.0) / 3.0;
+        let m = DMat4::reflection(n, 1.5);
+        let on_plane = dvec3!(4.0, 1.0, 0.0) - n * (n.dot(dvec3!(4.0, 1.0, 0.0)) + 1.5);
+        approx::assert_relative_eq!(m.transform_point(on_plane), on_plane, epsilon = 1.0e-12);
+        let off_plane = on_plane + n * 0.75;
+        approx::assert_relative_eq!(
+            m.transform_point(off_plane),
+            on_plane - n * 0.75,
+            epsilon = 1.0e-12
+        );
+        assert!(m.determinant() < 0.0);
+    }
+
+    #[test]
+    pub fn mat3_look_rotation() {
+        let forward = vec3!(1.0, -2.0, 0.5);
+        let m = Mat3::look_rotation(forward, vec3!(0.0, 1.0, 0.0));
+        approx::assert_relative_eq!(m * m.transpose(), Mat3::identity(), epsilon = 1.0e-6);
+        approx::assert_relative_eq!(m.determinant(), 1.0, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(
+            m * vec3!(0.0, 0.0, -1.0),
+            forward.normalize(),
+            epsilon = 1.0e-6
+        );
+        assert_eq!(m.m01, 0.0);
+        assert!(m.m11 > 0.0);
+
+        for &up in &[dvec3!(0.0, 0.0, 3.0), dvec3!(0.0, 0.0, -1.0), dvec3!(0.0)] {
+            let m = DMat3::look_rotation(dvec3!(0.0, 0.0, 2.0), up);
+            approx::assert_relative_eq!(m * m.transpose(), DMat3::identity(), epsilon = 1.0e-12);
+            approx::assert_relative_eq!(m.determinant(), 1.0, epsilon = 1.0e-12);
+            approx::assert_relative_eq!(m * dvec3!(0.0, 0.0, -1.0), dvec3!(0.0, 0.0, 1.0));
+        }
+    }
+
+    #[test]
+    pub fn mat2_from_angle_scale() {
+        use std::f32::consts::FRAC_PI_2;
+        let r = Mat2::from_angle(FRAC_PI_2);
+        approx::assert_relative_eq!(r * vec2!(1.0, 0.0), vec2!(0.0, 1.0));
+        approx::assert_relative_eq!(r * vec2!(0.0, 1.0), vec2!(-1.0, 0.0));
+        assert_eq!(
+            Mat2::from_scale(vec2!(2.0, 3.0)) * vec2!(1.0, -1.0),
+            vec2!(2.0, -3.0)
+        );
+
+        let r = DMat2::from_angle(0.7);
+        approx::assert_relative_eq!(r * r.transpose(), DMat2::identity(), epsilon = 1.0e-12);
+        approx::assert_relative_eq!(
+            Mat3::from(Mat2::from_angle(0.7)),
+            Mat3::from_angle(0.7),
+            epsilon = 1.0e-6
+        );
+        assert_eq!(
+            DMat2::from_scale(dvec2!(4.0, 5.0)),
+            DMat2::new(4.0, 0.0, 0.0, 5.0)
+        );
+    }
+
+    #[test]
+    pub fn mat_as_mut() {
+        let mut m = Mat3::identity();
+        m.as_mut()[2] = [4.0, 5.0, 6.0];
+        assert_eq!((m.m20, m.m21, m.m22), (4.0, 5.0, 6.0));
+        let mut m = DMat4::identity();
+        let a: &mut [[f64; 4]; 4] = m.as_mut();
+        a[3][1] = 7.0;
+        assert_eq!(m.m31, 7.0);
+    }
+
+    #[test]
+    pub fn mat4_transform_ray() {
+        use crate::Trs;
+        let m = Trs::new(
+            vec3!(1.0, 2.0, 3.0),
+            Quat::axis_angle(vec3!(0.0, 0.0, 1.0), std::f32::consts::FRAC_PI_2),
+            vec3!(1.0),
+        )
+        .matrix();
+        let (origin, dir) = m.transform_ray(vec3!(1.0, 0.0, 0.0), vec3!(2.0, 0.0, 0.0));
+        approx::assert_relative_eq!(origin, vec3!(1.0, 3.0, 3.0), epsilon = 1.0e-6);
+        approx::assert_relative_eq!(dir, vec3!(0.0, 2.0, 0.0), epsilon = 1.0e-6);
+
+        // A point at distance t along the ray maps to distance t along the result.
+        let m = DMat4::from(m).inverse();
+        let (o, d) = (dvec3!(-1.0, 4.0, 0.5), dvec3!(0.0, 0.0, 3.0));
+        let (origin, dir) = m.transform_ray(o, d);
+        approx::assert_relative_eq!(
+            m.transform_point(o + d * 1.5),
+            origin + dir * 1.5,
+            epsilon = 1.0e-12
+        );
+    }
+
+    #[test]
+    pub fn mat4_from_scale_translation() {
+        use crate::{DTrs, Trs};
+        let (s, t) = (vec3!(2.0, 0.5, -3.0), vec3!(1.0, -2.0, 4.0));
+        assert_eq!(
+            Mat4::from_scale_translation(s, t),
+            Trs::new(t, quat!(), s).matrix()
+        );
+        assert_eq!(
+            Mat4::from_scale_translation(s, t).transform_point(vec3!(1.0)),
+            vec3!(3.0, -1.5, 1.0)
+        );
+        let (s, t) = (dvec3!(0.25), dvec3!(7.0, 8.0, 9.0));
+        assert_eq!(
+            DMat4::from_scale_translation(s, t),
+            DTrs::new(t, dquat!(), s).matrix()
+        );
+    }
+
+    #[test]
+    pub fn mat_norms() {
+        let m = Mat2::new(1.0, -2.0, 2.0, 4.0);
+        assert_eq!(m.frobenius_norm(), 5.0);
+        assert_eq!(m.max_norm(), 4.0);
+        assert_eq!(Mat3::identity().frobenius_norm(), 3.0f32.sqrt());
+        assert_eq!(Mat4::zero().max_norm(), 0.0);
+
+        let mut m = DMat4::identity();
+        m.m21 = -7.0;
+        assert_eq!(m.max_norm(), 7.0);
+        assert_eq!((m - DMat4::identity()).frobenius_norm(), 7.0);
+        assert_eq!(m.frobenius_norm(), 53.0f64.sqrt());
+        m.m02 = f64::NAN;
+        assert!(m.max_norm().is_nan());
+        let mut m = Mat3::identity();
+        m.m00 = f32::NAN;
+        assert!(m.max_norm().is_nan());
+    }
+
+    #[test]
+    pub fn mat_transpose_mut() {
+        let m = Mat4::from_cols_array([
+            1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+        ]);
+        let mut t = m;
+        t.transpose_mut();
+        assert_eq!(t, m.transpose());
+        t.transpose_mut();
+        assert_eq!(t, m);
+
+        let m = DMat3::from_axis_angle(dvec3!(1.0, 2.0, 3.0), 0.4) * 2.0;
+        let mut t = m;
+        t.transpose_mut();
+        assert_eq!(t, m.transpose());
+
+        let mut t = Mat2::new(1.0, 2.0, 3.0, 4.0);
+        t.transpose_mut();
+        assert_eq!(t, Mat2::new(1.0, 3.0, 2.0, 4.0));
+    }
+}
+
 #[cfg(feature = "mint")]
 mod mint_support {
     use super::*;
@@ -1,10 +1,49 @@
 use cgmath;
 use std::{fmt, mem, ops};
 
-use crate::{DVec3, Vec3};
+use crate::scalar;
+use crate::{DMat3, DMat4, DVec3, Mat3, Mat4, Vec3};
 use approx::ApproxEq;
+use approx05::AbsDiffEq;
 use cgmath::{InnerSpace, Rotation3};
 
+/// Order in which the per-axis rotations of a set of Euler angles are
+/// composed.
+///
+/// For order `ABC`, the rotation is the quaternion product `A * B * C` of the
+/// rotations around each axis. Applied to a vector, the `C` rotation therefore
+/// happens first, followed by `B`, then `A`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EulerOrder {
+    /// `X * Y * Z`.
+    XYZ,
+    /// `X * Z * Y`.
+    XZY,
+    /// `Y * X * Z`.
+    YXZ,
+    /// `Y * Z * X`.
+    YZX,
+    /// `Z * X * Y`.
+    ZXY,
+    /// `Z * Y * X`.
+    ZYX,
+}
+
+impl EulerOrder {
+    /// Returns the axis indices in composition order and whether they form a
+    /// cyclic permutation of X, Y, Z.
+    fn axes(self) -> (usize, usize, usize, bool) {
+        match self {
+            EulerOrder::XYZ => (0, 1, 2, true),
+            EulerOrder::XZY => (0, 2, 1, false),
+            EulerOrder::YXZ => (1, 0, 2, false),
+            EulerOrder::YZX => (1, 2, 0, true),
+            EulerOrder::ZXY => (2, 0, 1, true),
+            EulerOrder::ZYX => (2, 1, 0, false),
+        }
+    }
+}
+
 /// Single-precision quaternion.
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[repr(C)]
@@ -17,31 +56,32 @@ pub struct Quat {
 
 impl fmt::Display for Quat {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({}, {}, {}; {})", self.x, self.y, self.z, self.s)
+        let components = [self.x, self.y, self.z, self.s];
+        scalar::fmt_components(f, &components, 3, fmt::Display::fmt)
     }
 }
 
 impl Quat {
     /// Full constructor.
-    pub fn new(x: f32, y: f32, z: f32, s: f32) -> Self {
+    pub const fn new(x: f32, y: f32, z: f32, s: f32) -> Self {
         Quat { x, y, z, s }
     }
 
     /// Identity constructor.
-    pub fn identity() -> Self {
+    pub const fn identity() -> Self {
         Quat::new(0.0, 0.0, 0.0, 1.0)
     }
 
     /// Constructor for a rotation defined by a set of Euler angles
     ///
-    /// The rotation order is Z, then X, then Y. From the point of the
-    /// object, this is equivalent to a yaw in `angles.y`, a pitch in
-    /// `angles.x`, and a roll in `angles.z`.
+    /// Equivalent to `rot_z(angles.z) * rot_x(angles.x) * rot_y(angles.y)`,
+    /// i.e. a yaw in `angles.y`, a pitch in `angles.x`, and a roll in
+    /// `angles.z`. Applied to a vector, the Y rotation happens first, then X,
+    /// then Z.
+    ///
+    /// Equivalent to `euler_with_order(angles, EulerOrder::ZXY)`.
     pub fn euler(angles: Vec3) -> Self {
-        let roll = quat!(0.0, 0.0, 1.0; angles.z);
-        let pitch = quat!(1.0, 0.0, 0.0; angles.x);
-        let yaw = quat!(0.0, 1.0, 0.0; angles.y);
-        roll * pitch * yaw
+        Self::euler_with_order(angles, EulerOrder::ZXY)
     }
 
     /// Constructor for a rotation around `axis` by `angle` radians.
@@ -64,6 +104,34 @@ impl Quat {
         let result = rotation.rotate_point(point);
         vec3!(result.x, result.y, result.z)
     }
+
+    /// Writes the rotation of each vector in `points` to the corresponding
+    /// element of `out`, as per `rotate`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `points` and `out` have different lengths.
+    pub fn rotate_points(&self, points: &[Vec3], out: &mut [Vec3]) {
+        use cgmath::Rotation;
+        assert_eq!(points.len(), out.len(), "slice length mismatch");
+        let rotation = cgmath::Quaternion::new(self.s, self.x, self.y, self.z);
+        for (p, o) in points.iter().zip(out.iter_mut()) {
+            let result = rotation.rotate_point(cgmath::Point3::new(p.x, p.y, p.z));
+            *o = vec3!(result.x, result.y, result.z);
+        }
+    }
+
+    /// Returns the rotation matrix represented by this quaternion, scaled
+    /// uniformly by `scale`.
+    pub fn to_mat3_scaled(self, scale: f32) -> Mat3 {
+        Mat3::from(self) * scale
+    }
+}
+
+impl From<DQuat> for Quat {
+    fn from(arg: DQuat) -> Self {
+        Quat::new(arg.x as f32, arg.y as f32, arg.z as f32, arg.s as f32)
+    }
 }
 
 /// Double-precision quaternion.
@@ -78,31 +146,32 @@ pub struct DQuat {
 
 impl fmt::Display for DQuat {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "({}, {}, {}; {})", self.x, self.y, self.z, self.s)
+        let components = [self.x, self.y, self.z, self.s];
+        scalar::fmt_components(f, &components, 3, fmt::Display::fmt)
     }
 }
 
 impl DQuat {
     /// Full constructor.
-    pub fn new(x: f64, y: f64, z: f64, s: f64) -> Self {
+    pub const fn new(x: f64, y: f64, z: f64, s: f64) -> Self {
         DQuat { x, y, z, s }
     }
 
     /// Identity constructor.
-    pub fn identity() -> Self {
+    pub const fn identity() -> Self {
         DQuat::new(0.0, 0.0, 0.0, 1.0)
     }
 
     /// Constructor for a rotation defined by a set of Euler angles
     ///
-    /// The rotation order is Z, then X, then Y. From the point of the
-    /// object, this is equivalent to a yaw in `angles.y`, a pitch in
-    /// `angles.x`, and a roll in `angles.z`.
+    /// Equivalent to `rot_z(angles.z) * rot_x(angles.x) * rot_y(angles.y)`,
+    /// i.e. a yaw in `angles.y`, a pitch in `angles.x`, and a roll in
+    /// `angles.z`. Applied to a vector, the Y rotation happens first, then X,
+    /// then Z.
+    ///
+    /// Equivalent to `euler_with_order(angles, EulerOrder::ZXY)`.
     pub fn euler(angles: DVec3) -> Self {
-        let roll = dquat!(0.0, 0.0, 1.0; angles.z);
-        let pitch = dquat!(1.0, 0.0, 0.0; angles.x);
-        let yaw = dquat!(0.0, 1.0, 0.0; angles.y);
-        roll * pitch * yaw
+        Self::euler_with_order(angles, EulerOrder::ZXY)
     }
 
     /// Constructor for a rotation around `axis` by `angle` radians.
@@ -125,44 +194,429 @@ impl DQuat {
         let result = rotation.rotate_point(point);
         dvec3!(result.x, result.y, result.z)
     }
+
+    /// Writes the rotation of each vector in `points` to the corresponding
+    /// element of `out`, as per `rotate`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `points` and `out` have different lengths.
+    pub fn rotate_points(&self, points: &[DVec3], out: &mut [DVec3]) {
+        use cgmath::Rotation;
+        assert_eq!(points.len(), out.len(), "slice length mismatch");
+        let rotation = cgmath::Quaternion::new(self.s, self.x, self.y, self.z);
+        for (p, o) in points.iter().zip(out.iter_mut()) {
+            let result = rotation.rotate_point(cgmath::Point3::new(p.x, p.y, p.z));
+            *o = dvec3!(result.x, result.y, result.z);
+        }
+    }
+
+    /// Returns the rotation matrix represented by this quaternion, scaled
+    /// uniformly by `scale`.
+    pub fn to_mat3_scaled(self, scale: f64) -> DMat3 {
+        DMat3::from(self) * scale
+    }
+}
+
+impl From<Quat> for DQuat {
+    fn from(arg: Quat) -> Self {
+        DQuat::new(arg.x as f64, arg.y as f64, arg.z as f64, arg.s as f64)
+    }
 }
 
 macro_rules! impl_quaternion {
-    ($self:ty, $base:ty, $inner:ty, $array:ty) => {
+    ($self:ty, $base:ty, $inner:ty, $array:ty, $vec3:ty, $mat3:ty, $mat4:ty) => {
+        impl $self {
+            /// Constructor for a rotation defined by a set of Euler angles,
+            /// composed in the given order.
+            ///
+            /// `angles.x`, `angles.y`, and `angles.z` are the rotations in
+            /// radians around the X, Y, and Z axes respectively.
+            pub fn euler_with_order(angles: $vec3, order: EulerOrder) -> Self {
+                let angles: [$base; 3] = angles.into();
+                let rotation = |axis: usize| {
+                    let mut q = [0.0, 0.0, 0.0, (0.5 * angles[axis]).cos()];
+                    q[axis] = (0.5 * angles[axis]).sin();
+                    Self::from(q)
+                };
+                let (i, j, k, _) = order.axes();
+                rotation(i) * rotation(j) * rotation(k)
+            }
+
+            /// Returns the Euler angles of the rotation for the given order,
+            /// such that `euler_with_order(q.to_euler(order), order)` represents
+            /// the same rotation as `q`.
+            ///
+            /// The angle of the middle axis is in the range `[-π/2, π/2]` and the
+            /// others are in the range `[-π, π]`. In gimbal lock, the angle of the
+            /// last axis is zero.
+            pub fn to_euler(self, order: EulerOrder) -> $vec3 {
+                let m = <$mat3>::from(self);
+                let (i, j, k, cyclic) = order.axes();
+                let sign = if cyclic { 1.0 } else { -1.0 };
+                let cos_middle = m[(i, i)].hypot(m[(i, j)]);
+                let mut angles = [0.0; 3];
+                angles[j] = (sign * m[(i, k)]).atan2(cos_middle);
+                if cos_middle > 16.0 * <$base>::EPSILON {
+                    angles[i] = (-sign * m[(j, k)]).atan2(m[(k, k)]);
+                    angles[k] = (-sign * m[(i, j)]).atan2(m[(i, i)]);
+                } else {
+                    angles[i] = (sign * m[(k, j)]).atan2(m[(j, j)]);
+                }
+                angles.into()
+            }
+
+            /// Returns the equivalent 3x3 rotation matrix, as per the `From`
+            /// conversion.
+            pub fn to_mat3(self) -> $mat3 {
+                <$mat3>::from(self)
+            }
+
+            /// Returns the equivalent 4x4 rotation matrix, as per the `From`
+            /// conversion.
+            pub fn to_mat4(self) -> $mat4 {
+                <$mat4>::from(self)
+            }
+
+            /// Constructor from an array in `[x, y, z, s]` order, with the scalar
+            /// part last, as used by glTF.
+            pub fn from_array_xyzw(a: $array) -> Self {
+                Self::new(a[0], a[1], a[2], a[3])
+            }
+
+            /// Constructor from an array in `[s, x, y, z]` order, with the scalar
+            /// part first.
+            pub fn from_array_wxyz(a: $array) -> Self {
+                Self::new(a[1], a[2], a[3], a[0])
+            }
+
+            /// Returns the components as an array in `[x, y, z, s]` order, with
+            /// the scalar part last, as used by glTF.
+            pub fn to_array_xyzw(self) -> $array {
+                [self.x, self.y, self.z, self.s]
+            }
+
+            /// Returns the components as an array in `[s, x, y, z]` order, with
+            /// the scalar part first.
+            pub fn to_array_wxyz(self) -> $array {
+                [self.s, self.x, self.y, self.z]
+            }
+
+            /// Checks that the quaternion is a valid rotation, i.e. that all its
+            /// components are finite and that it has unit length to within the
+            /// square root of machine epsilon.
+            ///
+            /// Returns a description of the first violated invariant otherwise.
+            pub fn check_invariants(self) -> Result<(), &'static str> {
+                let components: $array = self.into();
+                let q = <$inner>::new(self.s, self.x, self.y, self.z);
+                if components.iter().any(|c| c.is_nan()) {
+                    Err("quaternion has a NaN component")
+                } else if components.iter().any(|c| c.is_infinite()) {
+                    Err("quaternion has an infinite component")
+                } else if (q.magnitude() - 1.0).abs() > <$base>::EPSILON.sqrt() {
+                    Err("quaternion is not normalized")
+                } else {
+                    Ok(())
+                }
+            }
+
+            /// Returns the conjugate `(-x, -y, -z, s)`, which for a normalized
+            /// quaternion is the inverse rotation.
+            pub fn conjugate(self) -> Self {
+                Self::new(-self.x, -self.y, -self.z, self.s)
+            }
+
+            /// Returns the multiplicative inverse, such that `q * q.inverse()` is
+            /// the identity.
+            ///
+            /// Equal to `conjugate` for normalized quaternions. The result is
+            /// non-finite for the zero quaternion.
+            pub fn inverse(self) -> Self {
+                let q = <$inner>::new(self.s, self.x, self.y, self.z);
+                let q = q.conjugate() / q.magnitude2();
+                Self::new(q.v.x, q.v.y, q.v.z, q.s)
+            }
+
+            /// Returns the spherical linear interpolation between two rotations.
+            ///
+            /// The interpolation takes the shortest path between the rotations.
+            /// Values of `t` outside the range `[0, 1]` extrapolate beyond either
+            /// rotation; use `slerp_clamped` to prevent this. Both quaternions
+            /// should be normalized.
+            pub fn slerp(self, other: $self, t: $base) -> $self {
+                self.slerp_unclamped(other, t)
+            }
+
+            /// Returns the spherical linear interpolation between two rotations,
+            /// extrapolating along the same great circle for `t` outside the range
+            /// `[0, 1]`.
+            ///
+            /// The interpolation takes the shortest path between the rotations.
+            /// Both quaternions should be normalized.
+            pub fn slerp_unclamped(self, other: $self, t: $base) -> $self {
+                let a = <$inner>::new(self.s, self.x, self.y, self.z);
+                let b = <$inner>::new(other.s, other.x, other.y, other.z);
+                let q = scalar::slerp(a, b, t);
+                Self::new(q.v.x, q.v.y, q.v.z, q.s)
+            }
+
+            /// Returns the spherical linear interpolation between two rotations,
+            /// with `t` clamped to the range `[0, 1]` so that the result never
+            /// overshoots either rotation.
+            ///
+            /// The interpolation takes the shortest path between the rotations.
+            /// Both quaternions should be normalized.
+            pub fn slerp_clamped(self, other: $self, t: $base) -> $self {
+                self.slerp_unclamped(other, t.clamp(0.0, 1.0))
+            }
+
+            /// Returns the spherical linear interpolation between two rotations
+            /// along the longest path, i.e. the complement of the arc taken by
+            /// `slerp`.
+            ///
+            /// Both quaternions should be normalized. For identical rotations,
+            /// every full turn is equally long and one around an arbitrary axis
+            /// is taken.
+            pub fn slerp_long(self, other: $self, t: $base) -> $self {
+                let a = <$inner>::new(self.s, self.x, self.y, self.z);
+                let b = <$inner>::new(other.s, other.x, other.y, other.z);
+                let q = scalar::slerp_long(a, b, t);
+                Self::new(q.v.x, q.v.y, q.v.z, q.s)
+            }
+
+            /// Returns a distance between two rotations in the range `[0, 1]`,
+            /// computed as `1 - |self · other|`.
+            ///
+            /// The distance is 0 for identical rotations, including `q` and `-q`,
+            /// and 1 for rotations that differ by a half turn. Both quaternions
+            /// should be normalized.
+            pub fn distance(self, other: $self) -> $base {
+                let a = <$inner>::new(self.s, self.x, self.y, self.z);
+                let b = <$inner>::new(other.s, other.x, other.y, other.z);
+                1.0 - a.dot(b).abs()
+            }
+
+            /// Returns the angle in radians of the rotation taking `self` to
+            /// `other`, in the range `[0, PI]`.
+            ///
+            /// Both quaternions are normalized first. Since `q` and `-q` represent
+            /// the same rotation, the angle between them is 0.
+            pub fn angle_between(self, other: $self) -> $base {
+                let a = <$inner>::new(self.s, self.x, self.y, self.z).normalize();
+                let b = <$inner>::new(other.s, other.x, other.y, other.z).normalize();
+                2.0 * a.dot(b).abs().min(1.0).acos()
+            }
+
+            /// Returns `true` if the quaternion is within `epsilon` of the identity
+            /// rotation, component-wise.
+            ///
+            /// Both `(0, 0, 0, 1)` and `(0, 0, 0, -1)` represent the identity.
+            pub fn is_identity(self, epsilon: $base) -> bool {
+                self.x.abs() <= epsilon
+                    && self.y.abs() <= epsilon
+                    && self.z.abs() <= epsilon
+                    && (self.s.abs() - 1.0).abs() <= epsilon
+            }
+
+            /// Returns the rotation raised to the power `t`, i.e. the same rotation
+            /// axis with the angle scaled by `t`.
+            ///
+            /// The result is normalized. A quaternion with a zero vector part is
+            /// treated as the identity rotation.
+            pub fn pow(self, t: $base) -> $self {
+                let length = (self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+                if length == 0.0 {
+                    return Self::identity();
+                }
+                let half_angle = length.atan2(self.s) * t;
+                let k = half_angle.sin() / length;
+                Self::new(self.x * k, self.y * k, self.z * k, half_angle.cos())
+            }
+
+            /// Returns the quaternion exponential.
+            ///
+            /// For a pure quaternion `(θ n; 0)` with unit `n`, this is the rotation
+            /// by `2θ` around `n`.
+            pub fn exp(self) -> $self {
+                let length = (self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+                let k = if length > 0.0 {
+                    length.sin() / length
+                } else {
+                    1.0
+                };
+                let e = self.s.exp();
+                Self::new(
+                    e * k * self.x,
+                    e * k * self.y,
+                    e * k * self.z,
+                    e * length.cos(),
+                )
+            }
+
+            /// Returns the quaternion logarithm, the inverse of `exp`.
+            ///
+            /// The logarithm of a unit quaternion is a pure quaternion and the
+            /// logarithm of the identity is zero. A quaternion with a zero vector
+            /// part yields a zero vector part.
+            pub fn ln(self) -> $self {
+                let length = (self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+                let norm = (length * length + self.s * self.s).sqrt();
+                let k = if length > 0.0 {
+                    length.atan2(self.s) / length
+                } else {
+                    0.0
+                };
+                Self::new(k * self.x, k * self.y, k * self.z, norm.ln())
+            }
+
+            /// Returns the spherical quadrangle interpolation between the keyframes
+            /// `q1` and `q2`, with `q0` and `q3` being the keyframes before and
+            /// after them.
+            ///
+            /// The control quaternions are computed with `intermediate`, so that
+            /// consecutive segments of a keyframe sequence join with a continuous
+            /// angular velocity. Returns `q1` for `t = 0` and the rotation `q2` for
+            /// `t = 1`. Keyframes may be given as either `q` or `-q`, since each
+            /// is first moved into the hemisphere of its predecessor. All
+            /// keyframes should be normalized.
+            pub fn squad(q0: $self, q1: $self, q2: $self, q3: $self, t: $base) -> $self {
+                let q0 = q0.same_hemisphere(q1);
+                let q2 = q2.same_hemisphere(q1);
+                let q3 = q3.same_hemisphere(q2);
+                let s1 = Self::intermediate(q0, q1, q2);
+                let s2 = Self::intermediate(q1, q2, q3);
+                q1.slerp(q2, t).slerp(s1.slerp(s2, t), 2.0 * t * (1.0 - t))
+            }
+
+            /// Returns the `squad` control quaternion for the keyframe `current`
+            /// given its neighbouring keyframes `prev` and `next`.
+            ///
+            /// Keyframes may be given as either `q` or `-q`, since `prev` and
+            /// `next` are first moved into the hemisphere of `current`. All
+            /// keyframes should be normalized.
+            pub fn intermediate(prev: $self, current: $self, next: $self) -> $self {
+                let inverse = current.conjugate();
+                let a = (inverse * next.same_hemisphere(current)).ln();
+                let b = (inverse * prev.same_hemisphere(current)).ln();
+                let tangent = Self::new(
+                    -0.25 * (a.x + b.x),
+                    -0.25 * (a.y + b.y),
+                    -0.25 * (a.z + b.z),
+                    0.0,
+                );
+                current * tangent.exp()
+            }
+
+            /// Returns `self` or `-self`, whichever has a non-negative dot
+            /// product with `reference`.
+            fn same_hemisphere(self, reference: $self) -> $self {
+                let a = <$inner>::new(self.s, self.x, self.y, self.z);
+                let b = <$inner>::new(reference.s, reference.x, reference.y, reference.z);
+                if a.dot(b) < 0.0 {
+                    Self::new(-self.x, -self.y, -self.z, -self.s)
+                } else {
+                    self
+                }
+            }
+        }
+
         impl ops::Mul<$self> for $self {
             type Output = $self;
             fn mul(self, rhs: $self) -> $self {
-                let a: &$inner = self.as_ref().into();
-                let b: &$inner = rhs.as_ref().into();
+                let a = <$inner>::new(self.s, self.x, self.y, self.z);
+                let b = <$inner>::new(rhs.s, rhs.x, rhs.y, rhs.z);
                 let q = a * b;
                 Self::new(q.v.x, q.v.y, q.v.z, q.s)
             }
         }
 
+        /// Relative rotation.
+        ///
+        /// `b / a` is equivalent to `b * a⁻¹`, i.e. the rotation that when
+        /// applied after `a` yields `b`, such that `(b / a) * a == b`.
+        impl ops::Div<$self> for $self {
+            type Output = $self;
+            fn div(self, rhs: $self) -> $self {
+                let a = <$inner>::new(self.s, self.x, self.y, self.z);
+                let b = <$inner>::new(rhs.s, rhs.x, rhs.y, rhs.z);
+                let q = a * (b.conjugate() / b.magnitude2());
+                Self::new(q.v.x, q.v.y, q.v.z, q.s)
+            }
+        }
+
+        impl ops::DivAssign<$self> for $self {
+            fn div_assign(&mut self, rhs: $self) {
+                *self = *self / rhs;
+            }
+        }
+
         impl ops::MulAssign<$self> for $self {
             fn mul_assign(&mut self, rhs: $self) {
                 *self = *self * rhs;
             }
         }
 
+        /// Rotation of a vector, equivalent to `rotate`.
+        impl ops::Mul<$vec3> for $self {
+            type Output = $vec3;
+            fn mul(self, rhs: $vec3) -> Self::Output {
+                self.rotate(rhs)
+            }
+        }
+
+        impl<'a> ops::Mul<$vec3> for &'a $self {
+            type Output = $vec3;
+            fn mul(self, rhs: $vec3) -> Self::Output {
+                self.rotate(rhs)
+            }
+        }
+
         impl Default for $self {
             fn default() -> Self {
                 Self::identity()
             }
         }
 
+        impl From<$self> for $mat3 {
+            fn from(q: $self) -> Self {
+                let m: [[$base; 3]; 3] =
+                    cgmath::Matrix3::from(<$inner>::new(q.s, q.x, q.y, q.z)).into();
+                m.into()
+            }
+        }
+
+        impl From<$self> for $mat4 {
+            fn from(q: $self) -> Self {
+                let m: [[$base; 4]; 4] =
+                    cgmath::Matrix4::from(<$inner>::new(q.s, q.x, q.y, q.z)).into();
+                m.into()
+            }
+        }
+
+        // The quaternion is `#[repr(C)]` with its components in array order,
+        // hence it can be viewed as an array provided the size and alignment
+        // agree.
+        const _: () = assert!(
+            mem::size_of::<$self>() == mem::size_of::<$array>()
+                && mem::align_of::<$self>() == mem::align_of::<$array>()
+        );
+
         impl AsRef<$array> for $self {
             fn as_ref(&self) -> &$array {
-                unsafe { mem::transmute(self) }
+                unsafe { &*(self as *const $self as *const $array) }
             }
         }
 
+        /// Converts from an array in `[x, y, z, s]` order, as per
+        /// `from_array_xyzw`.
         impl From<$array> for $self {
             fn from(q: $array) -> Self {
                 Self::new(q[0], q[1], q[2], q[3])
             }
         }
 
+        /// Converts to an array in `[x, y, z, s]` order, as per `to_array_xyzw`.
         impl Into<$array> for $self {
             fn into(self) -> $array {
                 [self.x, self.y, self.z, self.s]
@@ -201,11 +655,631 @@ macro_rules! impl_quaternion {
                 a.ulps_eq(b, epsilon, max_ulps)
             }
         }
+
+        impl AbsDiffEq for $self {
+            type Epsilon = $base;
+
+            fn default_epsilon() -> Self::Epsilon {
+                <$base>::EPSILON
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                let a: &$array = self.as_ref();
+                let b: &$array = other.as_ref();
+                a.iter()
+                    .zip(b.iter())
+                    .all(|(x, y)| x.abs_diff_eq(y, epsilon))
+            }
+        }
     };
 }
 
-impl_quaternion!(DQuat, f64, cgmath::Quaternion<f64>, [f64; 4]);
-impl_quaternion!(Quat, f32, cgmath::Quaternion<f32>, [f32; 4]);
+impl_quaternion!(
+    DQuat,
+    f64,
+    cgmath::Quaternion<f64>,
+    [f64; 4],
+    DVec3,
+    DMat3,
+    DMat4
+);
+impl_quaternion!(
+    Quat,
+    f32,
+    cgmath::Quaternion<f32>,
+    [f32; 4],
+    Vec3,
+    Mat3,
+    Mat4
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn quat_mul() {
+        let a = quat!(1.0, 0.0, 0.0; 0.7);
+        let b = quat!(0.0, 1.0, 0.0; 0.4);
+        let v = vec3!(1.0, 2.0, 3.0);
+        approx::assert_relative_eq!((a * b).rotate(v), a.rotate(b.rotate(v)), epsilon = 1.0e-6);
+
+        let a = dquat!(1.0, 0.0, 0.0; 0.7);
+        let b = dquat!(0.0, 1.0, 0.0; 0.4);
+        let v = dvec3!(1.0, 2.0, 3.0);
+        approx::assert_relative_eq!((a * b).rotate(v), a.rotate(b.rotate(v)), epsilon = 1.0e-12);
+
+        // i * j = k, and a general product worked out by hand.
+        assert_eq!(
+            Quat::new(1.0, 0.0, 0.0, 0.0) * Quat::new(0.0, 1.0, 0.0, 0.0),
+            Quat::new(0.0, 0.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            Quat::new(1.0, 2.0, 3.0, 4.0) * Quat::new(5.0, 6.0, 7.0, 8.0),
+            Quat::new(24.0, 48.0, 48.0, -6.0)
+        );
+        assert_eq!(
+            DQuat::new(1.0, 2.0, 3.0, 4.0) * DQuat::new(5.0, 6.0, 7.0, 8.0),
+            DQuat::new(24.0, 48.0, 48.0, -6.0)
+        );
+    }
+
+    #[test]
+    pub fn quat_div() {
+        let a = quat!(1.0, 2.0, 0.0; 0.7);
+        let b = quat!(0.0, 1.0, -1.0; 2.4);
+        approx::assert_relative_eq!((b / a) * a, b, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(a / a, Quat::identity(), epsilon = 1.0e-6);
+
+        let mut c = b;
+        c /= a;
+        assert_eq!(c, b / a);
+
+        let a = dquat!(1.0, 2.0, 0.0; 0.7);
+        let b = dquat!(0.0, 1.0, -1.0; 2.4);
+        approx::assert_relative_eq!((b / a) * a, b, epsilon = 1.0e-12);
+    }
+
+    #[test]
+    pub fn quat_check_invariants() {
+        assert_eq!(Quat::identity().check_invariants(), Ok(()));
+        assert_eq!(quat!(1.0, 2.0, 3.0; 0.5).check_invariants(), Ok(()));
+        assert_eq!(dquat!(1.0, 2.0, 3.0; 0.5).check_invariants(), Ok(()));
+        assert!(Quat::new(f32::NAN, 0.0, 0.0, 1.0)
+            .check_invariants()
+            .is_err());
+        assert!(DQuat::new(0.0, 0.0, f64::INFINITY, 1.0)
+            .check_invariants()
+            .is_err());
+        assert!(Quat::new(0.0, 0.0, 0.0, 2.0).check_invariants().is_err());
+    }
+
+    #[test]
+    pub fn quat_to_matrix() {
+        let q = quat!(1.0, 2.0, 3.0; 0.75);
+        let v = vec3!(1.0, -1.0, 2.0);
+        approx::assert_relative_eq!(Mat3::from(q) * v, q.rotate(v), epsilon = 1.0e-6);
+        approx::assert_relative_eq!(
+            Mat4::from(q) * vec4!(v, 1.0),
+            vec4!(q.rotate(v), 1.0),
+            epsilon = 1.0e-6
+        );
+
+        let q = dquat!(1.0, 2.0, 3.0; 0.75);
+        let v = dvec3!(1.0, -1.0, 2.0);
+        approx::assert_relative_eq!(DMat3::from(q) * v, q.rotate(v), epsilon = 1.0e-12);
+    }
+
+    #[test]
+    pub fn quat_to_mat3_scaled() {
+        let q = quat!(0.0, 0.0, 1.0; std::f32::consts::PI / 2.0);
+        assert_eq!(q.to_mat3_scaled(2.0), Mat3::from(q) * 2.0);
+        approx::assert_relative_eq!(
+            q.to_mat3_scaled(2.0) * vec3!(1.0, 0.0, 0.0),
+            vec3!(0.0, 2.0, 0.0),
+            epsilon = 1.0e-6
+        );
+
+        let q = dquat!(1.0, 1.0, 0.0; 1.0);
+        assert_eq!(q.to_mat3_scaled(0.5), DMat3::from(q) * 0.5);
+    }
+
+    #[test]
+    pub fn quat_slerp_unclamped() {
+        let a = quat!(0.0, 1.0, 0.0; 0.25);
+        let b = quat!(0.0, 1.0, 0.0; 0.75);
+        approx::assert_relative_eq!(a.slerp_unclamped(b, 1.0), b, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(
+            a.slerp_unclamped(b, 2.0),
+            quat!(0.0, 1.0, 0.0; 1.25),
+            epsilon = 1.0e-6
+        );
+        approx::assert_relative_eq!(
+            a.slerp_unclamped(b, -1.0),
+            quat!(0.0, 1.0, 0.0; -0.25),
+            epsilon = 1.0e-6
+        );
+
+        let q = dquat!(1.0, 0.0, 0.0; 0.5);
+        approx::assert_relative_eq!(
+            DQuat::identity().slerp_unclamped(q, 2.0),
+            dquat!(1.0, 0.0, 0.0; 1.0),
+            epsilon = 1.0e-12
+        );
+    }
+
+    #[test]
+    pub fn quat_abs_diff_eq() {
+        let a = Quat::new(0.0, 0.6, 0.0, 0.8);
+        approx05::assert_abs_diff_eq!(a, Quat::new(0.0, 0.6, 1.0e-7, 0.8), epsilon = 1.0e-6);
+        approx05::assert_abs_diff_ne!(a, Quat::new(0.0, 0.8, 0.0, 0.6), epsilon = 1.0e-6);
+        approx05::assert_abs_diff_eq!(DQuat::identity(), DQuat::identity());
+    }
+
+    #[test]
+    pub fn quat_distance() {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        let a = quat!(1.0, 0.0, 0.0; 0.3);
+        let b = quat!(0.0, 1.0, 0.0; FRAC_PI_2);
+        let c = quat!(0.0, 0.0, 1.0; PI);
+        assert_eq!(a.distance(a), 0.0);
+        approx::assert_relative_eq!(a.distance(Quat::new(-a.x, -a.y, -a.z, -a.s)), 0.0);
+        assert!(Quat::identity().distance(b) > 0.0);
+        assert!(Quat::identity().distance(c) > Quat::identity().distance(b));
+        approx::assert_relative_eq!(Quat::identity().distance(c), 1.0, epsilon = 1.0e-6);
+        assert_eq!(a.distance(b), b.distance(a));
+        let a = DQuat::identity();
+        let b = dquat!(0.0, 1.0, 0.0; 0.5);
+        assert_eq!(a.distance(b), b.distance(a));
+        assert!(a.distance(b) > 0.0);
+    }
+
+    #[test]
+    pub fn quat_euler_with_order() {
+        let angles = vec3!(0.3, -0.7, 1.1);
+        let x = quat!(1.0, 0.0, 0.0; angles.x);
+        let y = quat!(0.0, 1.0, 0.0; angles.y);
+        let z = quat!(0.0, 0.0, 1.0; angles.z);
+        let expected = [
+            (EulerOrder::XYZ, x * y * z),
+            (EulerOrder::XZY, x * z * y),
+            (EulerOrder::YXZ, y * x * z),
+            (EulerOrder::YZX, y * z * x),
+            (EulerOrder::ZXY, z * x * y),
+            (EulerOrder::ZYX, z * y * x),
+        ];
+        for &(order, q) in expected.iter() {
+            let r = Quat::euler_with_order(angles, order);
+            approx::assert_relative_eq!(r, q, epsilon = 1.0e-6);
+            approx::assert_relative_eq!(r.to_euler(order), angles, epsilon = 1.0e-5);
+        }
+        assert_eq!(
+            Quat::euler(angles),
+            Quat::euler_with_order(angles, EulerOrder::ZXY)
+        );
+
+        let angles = dvec3!(-1.3, 1.2, 0.4);
+        for &(order, _) in expected.iter() {
+            let q = DQuat::euler_with_order(angles, order);
+            approx::assert_relative_eq!(q.to_euler(order), angles, epsilon = 1.0e-12);
+        }
+    }
+
+    #[test]
+    pub fn quat_to_euler_gimbal_lock() {
+        use std::f64::consts::FRAC_PI_2;
+        let orders = [
+            EulerOrder::XYZ,
+            EulerOrder::XZY,
+            EulerOrder::YXZ,
+            EulerOrder::YZX,
+            EulerOrder::ZXY,
+            EulerOrder::ZYX,
+        ];
+        for &order in orders.iter() {
+            let (i, j, k, _) = order.axes();
+            let mut angles = [0.0; 3];
+            angles[i] = 0.4;
+            angles[j] = -FRAC_PI_2;
+            angles[k] = 0.9;
+            let q = DQuat::euler_with_order(angles.into(), order);
+            let r = DQuat::euler_with_order(q.to_euler(order), order);
+            approx::assert_relative_eq!(DMat3::from(q), DMat3::from(r), epsilon = 1.0e-9);
+        }
+    }
+
+    #[test]
+    pub fn quat_precision_conversion() {
+        let q = quat!(1.0, -2.0, 0.5; 2.1);
+        let d = DQuat::from(q);
+        let length = (d.x * d.x + d.y * d.y + d.z * d.z + d.s * d.s).sqrt();
+        approx::assert_relative_eq!(length, 1.0, epsilon = 1.0e-6);
+        assert_eq!(Quat::from(d), q);
+
+        let d = dquat!(0.3, 0.3, -1.0; -0.8);
+        let q = Quat::from(d);
+        assert!(q.check_invariants().is_ok());
+        approx::assert_relative_eq!(DQuat::from(q), d, epsilon = 1.0e-7);
+    }
+
+    #[test]
+    pub fn quat_mul_vec3() {
+        let q1 = quat!(1.0, 2.0, 3.0; 0.9);
+        let q2 = quat!(0.0, -1.0, 0.5; -2.2);
+        let v = vec3!(0.5, -1.5, 2.0);
+        assert_eq!(q1 * v, q1.rotate(v));
+        let r = &q1;
+        assert_eq!(r * v, q1.rotate(v));
+        approx::assert_relative_eq!(q1 * (q2 * v), (q1 * q2) * v, epsilon = 1.0e-5);
+
+        let q = dquat!(1.0, 0.0, 0.0; std::f64::consts::FRAC_PI_2);
+        approx::assert_relative_eq!(q * dvec3!(0.0, 1.0, 0.0), dvec3!(0.0, 0.0, 1.0));
+        let r = &q;
+        assert_eq!(r * dvec3!(1.0, 2.0, 3.0), q.rotate(dvec3!(1.0, 2.0, 3.0)));
+    }
+
+    #[test]
+    pub fn quat_const_constructors() {
+        const UP: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+        const IDENTITY: Quat = Quat::identity();
+        const HALF_TURN: DQuat = DQuat::new(0.0, 0.0, 1.0, 0.0);
+        assert_eq!(IDENTITY * UP, UP);
+        approx::assert_relative_eq!(
+            HALF_TURN * DVec3::new(1.0, 0.0, 0.0),
+            dvec3!(-1.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    pub fn quat_array_view_field_order() {
+        let q = Quat::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(q.as_ref(), &[q.x, q.y, q.z, q.s]);
+        assert_eq!(q.as_ref(), &[1.0, 2.0, 3.0, 4.0]);
+        let q = DQuat::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(q.as_ref(), &[1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    pub fn quat_pow() {
+        let q = quat!(1.0, -2.0, 0.5; 2.4);
+        approx::assert_relative_eq!(q.pow(1.0), q, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(q.pow(0.5).pow(2.0), q, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(q.pow(0.5) * q.pow(0.5), q, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(q.pow(0.0), Quat::identity());
+        approx::assert_relative_eq!(q.pow(-1.0) * q, Quat::identity(), epsilon = 1.0e-6);
+        assert_eq!(Quat::identity().pow(0.3), Quat::identity());
+
+        let q = dquat!(0.0, 0.0, 1.0; 0.6);
+        approx::assert_relative_eq!(q.pow(2.5), dquat!(0.0, 0.0, 1.0; 1.5), epsilon = 1.0e-12);
+        assert!(q.pow(2.5).check_invariants().is_ok());
+    }
+
+    #[test]
+    pub fn quat_exp_ln() {
+        let q = quat!(0.5, 1.0, -2.0; 1.7);
+        approx::assert_relative_eq!(q.ln().exp(), q, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(q.ln().s, 0.0, epsilon = 1.0e-6);
+        assert_eq!(Quat::identity().ln(), Quat::new(0.0, 0.0, 0.0, 0.0));
+        assert_eq!(Quat::new(0.0, 0.0, 0.0, 0.0).exp(), Quat::identity());
+
+        let half_angle = 0.4;
+        let p = DQuat::new(0.0, half_angle, 0.0, 0.0);
+        approx::assert_relative_eq!(
+            p.exp(),
+            dquat!(0.0, 1.0, 0.0; 2.0 * half_angle),
+            epsilon = 1.0e-12
+        );
+        let q = dquat!(-1.0, 0.0, 3.0; 2.9);
+        approx::assert_relative_eq!(q.ln().exp(), q, epsilon = 1.0e-12);
+        let q = DQuat::new(0.1, 0.2, 0.3, 2.0);
+        approx::assert_relative_eq!(q.ln().exp(), q, epsilon = 1.0e-12);
+    }
+
+    #[test]
+    pub fn quat_squad() {
+        let axis = vec3!(1.0, 2.0, -1.0);
+        let keys = [
+            quat!(axis; 0.2),
+            quat!(axis; 0.7),
+            quat!(axis; 1.2),
+            quat!(axis; 1.7),
+        ];
+        approx::assert_relative_eq!(
+            Quat::intermediate(keys[0], keys[1], keys[2]),
+            keys[1],
+            epsilon = 1.0e-6
+        );
+        for &t in [0.0, 0.25, 0.5, 0.9, 1.0].iter() {
+            approx::assert_relative_eq!(
+                Quat::squad(keys[0], keys[1], keys[2], keys[3], t),
+                keys[1].slerp(keys[2], t),
+                epsilon = 1.0e-6
+            );
+        }
+
+        let keys = [
+            dquat!(1.0, 0.0, 0.0; 0.3),
+            dquat!(0.0, 1.0, 0.0; 0.9),
+            dquat!(0.0, 1.0, 1.0; -0.4),
+            dquat!(1.0, 1.0, 0.0; 1.5),
+        ];
+        assert_eq!(
+            DQuat::squad(keys[0], keys[1], keys[2], keys[3], 0.0),
+            keys[1]
+        );
+        approx::assert_ulps_eq!(
+            DQuat::squad(keys[0], keys[1], keys[2], keys[3], 1.0),
+            keys[2]
+        );
+        let mid = DQuat::squad(keys[0], keys[1], keys[2], keys[3], 0.5);
+        assert!(mid.check_invariants().is_ok());
+    }
+
+    #[test]
+    pub fn quat_squad_keyframe_sign() {
+        let negate = |q: Quat| Quat::new(-q.x, -q.y, -q.z, -q.s);
+        let axis = vec3!(1.0, 2.0, -1.0);
+        let keys = [
+            quat!(axis; 0.2),
+            quat!(axis; 0.7),
+            quat!(axis; 1.2),
+            quat!(axis; 1.7),
+        ];
+        for i in 0..4 {
+            let mut flipped = keys;
+            flipped[i] = negate(keys[i]);
+            for &t in &[0.0, 0.25, 0.5, 0.9, 1.0] {
+                approx::assert_relative_eq!(
+                    Quat::squad(flipped[0], flipped[1], flipped[2], flipped[3], t).to_mat3(),
+                    Quat::squad(keys[0], keys[1], keys[2], keys[3], t).to_mat3(),
+                    epsilon = 1.0e-6
+                );
+            }
+        }
+        approx::assert_relative_eq!(
+            Quat::intermediate(keys[0], negate(keys[1]), keys[2]).to_mat3(),
+            Quat::intermediate(keys[0], keys[1], keys[2]).to_mat3(),
+            epsilon = 1.0e-6
+        );
+    }
+
+    #[test]
+    pub fn quat_display_precision() {
+        assert_eq!(format!("{}", Quat::identity()), "(0, 0, 0; 1)");
+        assert_eq!(
+            format!("{:.3}", DQuat::new(0.5, 0.0, 0.0, 0.8660254)),
+            "(0.500, 0.000, 0.000; 0.866)"
+        );
+    }
+
+    #[test]
+    pub fn quat_euler_rotation_sequence() {
+        use std::f32::consts::FRAC_PI_2;
+        let (px, py, pz) = (0.4, -1.1, 2.3);
+        let roll = Quat::axis_angle(vec3!(0.0, 0.0, 1.0), pz);
+        let pitch = Quat::axis_angle(vec3!(1.0, 0.0, 0.0), px);
+        let yaw = Quat::axis_angle(vec3!(0.0, 1.0, 0.0), py);
+        approx::assert_relative_eq!(
+            Quat::euler(vec3!(px, py, pz)),
+            roll * pitch * yaw,
+            epsilon = 1.0e-6
+        );
+
+        // Applied to a vector, the yaw happens first, then the pitch.
+        let q = Quat::euler(vec3!(FRAC_PI_2, FRAC_PI_2, 0.0));
+        approx::assert_relative_eq!(
+            q * vec3!(1.0, 0.0, 0.0),
+            vec3!(0.0, 1.0, 0.0),
+            epsilon = 1.0e-6
+        );
+
+        let (px, py, pz) = (-0.7, 0.2, 1.9);
+        let roll = DQuat::axis_angle(dvec3!(0.0, 0.0, 1.0), pz);
+        let pitch = DQuat::axis_angle(dvec3!(1.0, 0.0, 0.0), px);
+        let yaw = DQuat::axis_angle(dvec3!(0.0, 1.0, 0.0), py);
+        approx::assert_relative_eq!(
+            DQuat::euler(dvec3!(px, py, pz)),
+            roll * pitch * yaw,
+            epsilon = 1.0e-12
+        );
+    }
+
+    #[test]
+    pub fn quat_angle_between() {
+        use std::f32::consts::FRAC_PI_2;
+        let q = Quat::axis_angle(vec3!(1.0, 2.0, 3.0), 0.8);
+        assert_eq!(q.angle_between(q), 0.0);
+        assert_eq!(q.angle_between(Quat::new(-q.x, -q.y, -q.z, -q.s)), 0.0);
+        let r = Quat::axis_angle(vec3!(0.0, 1.0, 0.0), FRAC_PI_2);
+        approx::assert_relative_eq!(q.angle_between(q * r), FRAC_PI_2, epsilon = 1.0e-5);
+        approx::assert_relative_eq!(
+            (r * q).angle_between(Quat::new(2.0 * q.x, 2.0 * q.y, 2.0 * q.z, 2.0 * q.s)),
+            FRAC_PI_2,
+            epsilon = 1.0e-5
+        );
+
+        let q = DQuat::axis_angle(dvec3!(1.0, 0.0, 0.0), 0.3);
+        let r = DQuat::axis_angle(dvec3!(1.0, 0.0, 0.0), 2.8);
+        approx::assert_relative_eq!(q.angle_between(r), 2.5, epsilon = 1.0e-12);
+    }
+
+    #[test]
+    pub fn quat_slerp_long() {
+        use std::f32::consts::PI;
+        let z = vec3!(0.0, 0.0, 1.0);
+        let a = Quat::identity();
+        for &angle in &[0.3, 3.0] {
+            let b = Quat::axis_angle(z, angle);
+            for &t in &[0.0, 1.0] {
+                approx::assert_relative_eq!(
+                    a.slerp_long(b, t).to_mat3(),
+                    a.slerp(b, t).to_mat3(),
+                    epsilon = 1.0e-6
+                );
+            }
+            let short = a.slerp(b, 0.5);
+            let long = a.slerp_long(b, 0.5);
+            approx::assert_relative_eq!(
+                short.to_mat3(),
+                Quat::axis_angle(z, angle / 2.0).to_mat3(),
+                epsilon = 1.0e-6
+            );
+            approx::assert_relative_eq!(
+                long.to_mat3(),
+                Quat::axis_angle(z, angle / 2.0 - PI).to_mat3(),
+                epsilon = 1.0e-6
+            );
+            approx::assert_relative_eq!(short.angle_between(long), PI, epsilon = 1.0e-6);
+        }
+
+        // Supplying the negated quaternion does not change either path.
+        let b = DQuat::axis_angle(dvec3!(1.0, 1.0, 0.0), 2.9);
+        let nb = DQuat::new(-b.x, -b.y, -b.z, -b.s);
+        let a = DQuat::identity();
+        approx::assert_relative_eq!(a.slerp(b, 0.3), a.slerp(nb, 0.3), epsilon = 1.0e-12);
+        approx::assert_relative_eq!(
+            a.slerp_long(b, 0.3),
+            a.slerp_long(nb, 0.3),
+            epsilon = 1.0e-12
+        );
+    }
+
+    #[test]
+    pub fn quat_slerp_long_degenerate() {
+        use std::f32::consts::PI;
+        let axis = vec3!(1.0, 2.0, 3.0);
+        let a = Quat::axis_angle(axis, 0.7);
+        for &offset in &[0.0, 1.0e-4, 2.0 * PI - 1.0e-4] {
+            // Nearly identical and, for the last offset, nearly antipodal
+            // quaternions representing nearly identical rotations.
+            let b = Quat::axis_angle(axis, 0.7 + offset);
+            for &t in &[0.0, 0.25, 0.5, 0.75, 1.0] {
+                let long = a.slerp_long(b, t);
+                approx::assert_relative_eq!(
+                    long.to_array_xyzw().iter().map(|c| c * c).sum::<f32>(),
+                    1.0,
+                    epsilon = 1.0e-6
+                );
+            }
+            // The paths agree at the endpoints but diverge by a full turn,
+            // i.e. a half turn at the midpoint.
+            for &t in &[0.0, 1.0] {
+                approx::assert_relative_eq!(
+                    a.slerp_long(b, t).to_mat3(),
+                    a.slerp(b, t).to_mat3(),
+                    epsilon = 1.0e-5
+                );
+            }
+            approx::assert_relative_eq!(
+                a.slerp(b, 0.5).angle_between(a.slerp_long(b, 0.5)),
+                PI,
+                epsilon = 1.0e-5
+            );
+        }
+
+        let a = DQuat::axis_angle(dvec3!(0.0, 1.0, 0.0), 2.0);
+        let b = DQuat::new(-a.x, -a.y, -a.z, -a.s);
+        for &t in &[0.0, 0.5, 1.0] {
+            assert!(a.slerp_long(b, t).check_invariants().is_ok());
+            assert!(DQuat::identity()
+                .slerp_long(dquat!(), t)
+                .check_invariants()
+                .is_ok());
+        }
+        approx::assert_relative_eq!(a.slerp_long(b, 1.0), b, epsilon = 1.0e-12);
+    }
+
+    #[test]
+    pub fn quat_to_mat() {
+        use crate::{DMat3, DMat4, Mat3, Mat4};
+        let q = quat!(1.0, 2.0, 3.0; 0.9);
+        assert_eq!(q.to_mat3(), Mat3::from(q));
+        assert_eq!(q.to_mat4(), Mat4::from(q));
+        let q = DQuat::from(q);
+        assert_eq!(q.to_mat3(), DMat3::from(q));
+        assert_eq!(q.to_mat4(), DMat4::from(q));
+    }
+
+    #[test]
+    pub fn quat_rotate_points() {
+        let q = quat!(1.0, 2.0, 3.0; 0.9);
+        let points = [vec3!(1.0, 0.0, 0.0), vec3!(0.5, -2.0, 3.0), vec3!(0.0)];
+        let mut out = [vec3!(0.0); 3];
+        q.rotate_points(&points, &mut out);
+        for (p, o) in points.iter().zip(out.iter()) {
+            assert_eq!(*o, q.rotate(*p));
+        }
+
+        let q = DQuat::from(q);
+        let points = [dvec3!(4.0, 5.0, 6.0)];
+        let mut out = [dvec3!(0.0)];
+        q.rotate_points(&points, &mut out);
+        assert_eq!(out[0], q.rotate(points[0]));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn quat_rotate_points_length_mismatch() {
+        Quat::identity().rotate_points(&[vec3!(1.0); 3], &mut [vec3!(0.0); 2]);
+    }
+
+    #[test]
+    pub fn quat_array_order() {
+        let q = Quat::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(q.to_array_xyzw(), [1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(q.to_array_wxyz(), [4.0, 1.0, 2.0, 3.0]);
+        let a: [f32; 4] = q.into();
+        assert_eq!(a, q.to_array_xyzw());
+        assert_eq!(Quat::from_array_xyzw([1.0, 2.0, 3.0, 4.0]), q);
+        assert_eq!(Quat::from_array_wxyz([4.0, 1.0, 2.0, 3.0]), q);
+        assert_eq!(Quat::from([1.0, 2.0, 3.0, 4.0]), q);
+
+        let q = DQuat::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(DQuat::from_array_wxyz(q.to_array_wxyz()), q);
+        assert_ne!(DQuat::from_array_xyzw(q.to_array_wxyz()), q);
+    }
+
+    #[test]
+    pub fn quat_is_identity() {
+        assert!(Quat::identity().is_identity(0.0));
+        assert!(Quat::new(0.0, 0.0, 0.0, -1.0).is_identity(0.0));
+        assert!(Quat::new(1.0e-7, 0.0, -1.0e-7, 1.0).is_identity(1.0e-6));
+        assert!(!Quat::axis_angle(vec3!(0.0, 1.0, 0.0), 0.1).is_identity(1.0e-6));
+        assert!(!Quat::new(0.0, 0.0, 0.0, 0.5).is_identity(1.0e-6));
+        assert!(
+            DQuat::axis_angle(dvec3!(1.0, 0.0, 0.0), std::f64::consts::TAU).is_identity(1.0e-12)
+        );
+    }
+
+    #[test]
+    pub fn quat_slerp_clamped() {
+        let a = Quat::axis_angle(vec3!(0.0, 1.0, 0.0), 0.2);
+        let b = Quat::axis_angle(vec3!(0.0, 1.0, 0.0), 1.2);
+        assert_eq!(a.slerp_clamped(b, 1.5), a.slerp_unclamped(b, 1.0));
+        assert_eq!(a.slerp_clamped(b, -0.5), a.slerp_unclamped(b, 0.0));
+        assert_eq!(a.slerp_clamped(b, 0.3), a.slerp(b, 0.3));
+        assert_ne!(a.slerp_unclamped(b, 1.5), a.slerp_unclamped(b, 1.0));
+
+        let a = DQuat::identity();
+        let b = DQuat::axis_angle(dvec3!(1.0, 0.0, 0.0), 2.0);
+        assert_eq!(a.slerp_clamped(b, 7.0), a.slerp_unclamped(b, 1.0));
+    }
+
+    #[test]
+    pub fn quat_conjugate_inverse() {
+        let q = quat!(1.0, 2.0, 3.0; 0.75);
+        approx::assert_relative_eq!(q.conjugate(), Quat::new(-q.x, -q.y, -q.z, q.s));
+        approx::assert_relative_eq!(q * q.conjugate(), quat!(), epsilon = 1.0e-6);
+        approx::assert_relative_eq!(q.inverse(), q.conjugate(), epsilon = 1.0e-6);
+
+        let q = Quat::new(0.0, 2.0, 0.0, 2.0);
+        approx::assert_relative_eq!(q * q.inverse(), quat!(), epsilon = 1.0e-6);
+        approx::assert_relative_eq!(q.inverse() * q, quat!(), epsilon = 1.0e-6);
+
+        let q = DQuat::new(1.0, -2.0, 0.5, 3.0);
+        approx::assert_relative_eq!(q * q.inverse(), dquat!(), epsilon = 1.0e-12);
+    }
+}
 
 #[cfg(feature = "mint")]
 mod mint_support {
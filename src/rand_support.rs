@@ -0,0 +1,132 @@
+//! Random sampling of vectors and rotations with the `rand` crate.
+//!
+//! * `Standard` samples vectors with components in the range `[0, 1)` and
+//!   quaternions uniformly distributed over all rotations.
+//! * `UniformComponents` samples vectors with components in a given range.
+//! * `UnitSphere` samples directions uniformly distributed on the unit sphere.
+
+use crate::{DQuat, DVec2, DVec3, DVec4, Quat, Vec2, Vec3, Vec4};
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+/// Distribution of vectors whose components are sampled uniformly from the
+/// half-open range `[low, high)`, component-wise.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UniformComponents<V> {
+    low: V,
+    high: V,
+}
+
+impl<V> UniformComponents<V> {
+    /// Distribution constructor.
+    pub fn new(low: V, high: V) -> Self {
+        UniformComponents { low, high }
+    }
+}
+
+/// Distribution of directions uniformly distributed on the unit sphere.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct UnitSphere;
+
+macro_rules! impl_vector_distributions {
+    ($self:ident, $base:ty, $($field:ident),*) => {
+        impl Distribution<$self> for Standard {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $self {
+                $self::new($({ let $field: $base = rng.gen(); $field }),*)
+            }
+        }
+
+        impl Distribution<$self> for UniformComponents<$self> {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $self {
+                $self::new($({
+                    let t: $base = rng.gen();
+                    self.low.$field + (self.high.$field - self.low.$field) * t
+                }),*)
+            }
+        }
+    };
+}
+
+impl_vector_distributions!(Vec2, f32, x, y);
+impl_vector_distributions!(Vec3, f32, x, y, z);
+impl_vector_distributions!(Vec4, f32, x, y, z, w);
+impl_vector_distributions!(DVec2, f64, x, y);
+impl_vector_distributions!(DVec3, f64, x, y, z);
+impl_vector_distributions!(DVec4, f64, x, y, z, w);
+
+macro_rules! impl_rotation_distributions {
+    ($quat:ident, $vec3:ident, $base:ident) => {
+        impl Distribution<$vec3> for UnitSphere {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $vec3 {
+                let z: $base = rng.gen_range(-1.0..=1.0);
+                let phi: $base = rng.gen_range(0.0..(2.0 * ::std::$base::consts::PI));
+                let r = (1.0 - z * z).max(0.0).sqrt();
+                $vec3::new(r * phi.cos(), r * phi.sin(), z)
+            }
+        }
+
+        /// Samples rotations uniformly using Shoemake's method.
+        impl Distribution<$quat> for Standard {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $quat {
+                let tau = 2.0 * ::std::$base::consts::PI;
+                let (u1, u2, u3): ($base, $base, $base) = rng.gen();
+                let a = (1.0 - u1).sqrt();
+                let b = u1.sqrt();
+                $quat::new(
+                    a * (tau * u2).sin(),
+                    a * (tau * u2).cos(),
+                    b * (tau * u3).sin(),
+                    b * (tau * u3).cos(),
+                )
+            }
+        }
+    };
+}
+
+impl_rotation_distributions!(Quat, Vec3, f32);
+impl_rotation_distributions!(DQuat, DVec3, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    pub fn rand_unit_sphere() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let v: Vec3 = rng.sample(UnitSphere);
+            approx::assert_relative_eq!(v.length(), 1.0, epsilon = 1.0e-6);
+            let v: DVec3 = rng.sample(UnitSphere);
+            approx::assert_relative_eq!(v.length(), 1.0, epsilon = 1.0e-12);
+        }
+    }
+
+    #[test]
+    pub fn rand_rotation() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let q: Quat = rng.gen();
+            assert!(q.check_invariants().is_ok());
+            let q: DQuat = rng.gen();
+            assert!(q.check_invariants().is_ok());
+        }
+    }
+
+    #[test]
+    pub fn rand_uniform_components() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let dist = UniformComponents::new(vec3!(-1.0, 2.0, 5.0), vec3!(1.0, 3.0, 5.0));
+        for _ in 0..1000 {
+            let v: Vec3 = rng.sample(dist);
+            assert!(v.x >= -1.0 && v.x < 1.0);
+            assert!(v.y >= 2.0 && v.y < 3.0);
+            assert_eq!(v.z, 5.0);
+            let v: Vec4 = rng.gen();
+            assert!([v.x, v.y, v.z, v.w]
+                .iter()
+                .all(|&c| (0.0..1.0).contains(&c)));
+        }
+    }
+}
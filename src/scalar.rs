@@ -0,0 +1,238 @@
+//! Precision-generic algorithms shared by the single- and double-precision types.
+//!
+//! The public types are implemented once per precision by the `impl_*` macros.
+//! Algorithms that are more than a line or two long are written here once,
+//! generic over `Scalar`, and the macros delegate to them.
+
+use cgmath::{self, InnerSpace};
+use std::fmt;
+
+/// Floating point type underlying the vector, matrix, and quaternion types.
+pub(crate) trait Scalar: cgmath::BaseFloat {
+    /// Converts an `f64` constant to this type.
+    fn constant(x: f64) -> Self;
+}
+
+impl Scalar for f32 {
+    fn constant(x: f64) -> Self {
+        x as f32
+    }
+}
+
+impl Scalar for f64 {
+    fn constant(x: f64) -> Self {
+        x
+    }
+}
+
+/// Applies `f` to each component of `a`.
+pub(crate) fn map<S: Scalar, const N: usize>(mut a: [S; N], f: impl Fn(S) -> S) -> [S; N] {
+    for x in a.iter_mut() {
+        *x = f(*x);
+    }
+    a
+}
+
+/// Applies `f` to each pair of corresponding components of `a` and `b`.
+pub(crate) fn zip_map<S: Scalar, const N: usize>(
+    mut a: [S; N],
+    b: [S; N],
+    f: impl Fn(S, S) -> S,
+) -> [S; N] {
+    for (x, y) in a.iter_mut().zip(b.iter()) {
+        *x = f(*x, *y);
+    }
+    a
+}
+
+/// Writes `components` in parentheses, separated by `", "` within groups of
+/// `group` components and by `"; "` between groups.
+///
+/// Each component is written with `write_component`, which receives the
+/// formatter so that options such as precision and width apply per component.
+pub(crate) fn fmt_components<S>(
+    f: &mut fmt::Formatter,
+    components: &[S],
+    group: usize,
+    write_component: fn(&S, &mut fmt::Formatter) -> fmt::Result,
+) -> fmt::Result {
+    f.write_str("(")?;
+    for (i, c) in components.iter().enumerate() {
+        if i > 0 {
+            f.write_str(if i % group == 0 { "; " } else { ", " })?;
+        }
+        write_component(c, f)?;
+    }
+    f.write_str(")")
+}
+
+/// Hermite interpolation of `x` between `edge0` and `edge1`, clamped to the
+/// range `[0, 1]`.
+pub(crate) fn smoothstep<S: Scalar>(edge0: S, edge1: S, x: S) -> S {
+    let t = ((x - edge0) / (edge1 - edge0)).max(S::zero()).min(S::one());
+    t * t * (S::constant(3.0) - S::constant(2.0) * t)
+}
+
+/// Quintic Hermite interpolation of `x` between `edge0` and `edge1`, clamped to
+/// the range `[0, 1]`, with zero first and second derivatives at the edges.
+pub(crate) fn smootherstep<S: Scalar>(edge0: S, edge1: S, x: S) -> S {
+    let t = ((x - edge0) / (edge1 - edge0)).max(S::zero()).min(S::one());
+    t * t * t * (t * (t * S::constant(6.0) - S::constant(15.0)) + S::constant(10.0))
+}
+
+/// Applies the sRGB electro-optical transfer function, converting an
+/// sRGB-encoded value in `[0, 1]` to linear light.
+pub(crate) fn srgb_to_linear<S: Scalar>(x: S) -> S {
+    if x <= S::constant(0.04045) {
+        x / S::constant(12.92)
+    } else {
+        ((x + S::constant(0.055)) / S::constant(1.055)).powf(S::constant(2.4))
+    }
+}
+
+/// Applies the inverse sRGB transfer function, converting a linear light value
+/// in `[0, 1]` to its sRGB encoding.
+pub(crate) fn linear_to_srgb<S: Scalar>(x: S) -> S {
+    if x <= S::constant(0.0031308) {
+        x * S::constant(12.92)
+    } else {
+        S::constant(1.055) * x.powf(S::constant(1.0 / 2.4)) - S::constant(0.055)
+    }
+}
+
+/// Computes the rank of a square matrix by Gaussian elimination, treating
+/// pivots with magnitude less than or equal to `epsilon` as zero.
+pub(crate) fn rank<S: Scalar, const N: usize>(mut a: [[S; N]; N], epsilon: S) -> usize {
+    let mut rank = 0;
+    for c in 0..N {
+        if rank == N {
+            break;
+        }
+        let mut pivot = rank;
+        for r in (rank + 1)..N {
+            if a[r][c].abs() > a[pivot][c].abs() {
+                pivot = r;
+            }
+        }
+        if a[pivot][c].abs() <= epsilon {
+            continue;
+        }
+        a.swap(rank, pivot);
+        let (upper, lower) = a.split_at_mut(rank + 1);
+        let pivot_row = &upper[rank];
+        for row in lower.iter_mut() {
+            let f = row[c] / pivot_row[c];
+            for (x, p) in row[c..].iter_mut().zip(pivot_row[c..].iter()) {
+                *x -= f * *p;
+            }
+        }
+        rank += 1;
+    }
+    rank
+}
+
+/// Returns the spherical linear interpolation between two quaternions along
+/// the shortest path, extrapolating for `t` outside the range `[0, 1]`.
+pub(crate) fn slerp<S: Scalar>(
+    a: cgmath::Quaternion<S>,
+    b: cgmath::Quaternion<S>,
+    t: S,
+) -> cgmath::Quaternion<S> {
+    if a.dot(b) < S::zero() {
+        slerp_arc(a, -b, t)
+    } else {
+        slerp_arc(a, b, t)
+    }
+}
+
+/// Returns the spherical linear interpolation between two quaternions along
+/// the longest path, extrapolating for `t` outside the range `[0, 1]`.
+pub(crate) fn slerp_long<S: Scalar>(
+    a: cgmath::Quaternion<S>,
+    b: cgmath::Quaternion<S>,
+    t: S,
+) -> cgmath::Quaternion<S> {
+    if a.dot(b) > S::zero() {
+        slerp_arc(a, -b, t)
+    } else {
+        slerp_arc(a, b, t)
+    }
+}
+
+/// Returns the spherical linear interpolation between two quaternions along
+/// the great circle arc from `a` to `b`, without choosing between `b` and `-b`.
+fn slerp_arc<S: Scalar>(
+    a: cgmath::Quaternion<S>,
+    b: cgmath::Quaternion<S>,
+    t: S,
+) -> cgmath::Quaternion<S> {
+    let dot = a.dot(b).max(-S::one()).min(S::one());
+    if dot > S::constant(0.9995) {
+        a.nlerp(b, t)
+    } else if dot < S::constant(-0.9995) {
+        // The arc is nearly a half circle, so dividing by `sin(theta)` would
+        // amplify rounding errors without bound. Instead, make a half turn
+        // through a quaternion perpendicular to `a` while interpolating the
+        // small offset between `-a` and `b`.
+        let axis = if a.v.magnitude2() > S::default_epsilon() {
+            orthonormal(a.v.normalize())
+        } else {
+            cgmath::Vector3::unit_x()
+        };
+        let (sin, cos) = (S::constant(std::f64::consts::PI) * t).sin_cos();
+        a.nlerp(-b, t) * cgmath::Quaternion::from_sv(cos, axis * sin)
+    } else {
+        let (wa, wb) = arc_weights(dot, t);
+        a * wa + b * wb
+    }
+}
+
+/// Returns the spherical linear interpolation between two unit directions,
+/// rotating at constant angular velocity from `a` to `b`.
+///
+/// Nearly parallel directions are linearly interpolated and renormalized
+/// instead. The result is undefined for opposite directions.
+pub(crate) fn slerp_direction<S: Scalar>(a: [S; 3], b: [S; 3], t: S) -> [S; 3] {
+    let (a, b) = (cgmath::Vector3::from(a), cgmath::Vector3::from(b));
+    let dot = a.dot(b).max(-S::one()).min(S::one());
+    if dot > S::constant(0.9995) {
+        a.lerp(b, t).normalize().into()
+    } else {
+        let (wa, wb) = arc_weights(dot, t);
+        (a * wa + b * wb).into()
+    }
+}
+
+/// Returns the weights of the endpoints of a great circle arc whose endpoints
+/// have the dot product `dot`, for the point at parameter `t` along the arc.
+fn arc_weights<S: Scalar>(dot: S, t: S) -> (S, S) {
+    let theta = dot.acos();
+    let sin_theta = theta.sin();
+    (
+        ((S::one() - t) * theta).sin() / sin_theta,
+        (t * theta).sin() / sin_theta,
+    )
+}
+
+/// Returns a unit vector perpendicular to the unit vector `n`.
+fn orthonormal<S: Scalar>(n: cgmath::Vector3<S>) -> cgmath::Vector3<S> {
+    let sign = S::one().copysign(n.z);
+    let a = -S::one() / (sign + n.z);
+    cgmath::Vector3::new(
+        S::one() + sign * n.x * n.x * a,
+        sign * n.x * n.y * a,
+        -sign * n.x,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn scalar_zip_map() {
+        assert_eq!(zip_map([1.0f32, 2.0], [3.0, 4.0], |a, b| a * b), [3.0, 8.0]);
+        assert_eq!(zip_map([1.0f64; 3], [2.0; 3], f64::max), [2.0; 3]);
+        assert_eq!(map([1.0f32, -2.0], f32::abs), [1.0, 2.0]);
+    }
+}
@@ -0,0 +1,113 @@
+//! Serialization with the `serde` crate.
+//!
+//! * `TrsEulerRepr` and `DTrsEulerRepr` serialize transforms with the rotation
+//!   written as Euler angles in degrees, for hand-edited configuration files.
+
+use crate::{DQuat, DTrs, DVec3, EulerOrder, Quat, Trs, Vec3};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serialized form of a transform with the rotation as Euler angles.
+#[derive(Deserialize, Serialize)]
+struct EulerRepr<S> {
+    translation: [S; 3],
+    rotation: [S; 3],
+    scale: [S; 3],
+}
+
+macro_rules! impl_trs_euler_repr {
+    ($repr:ident, $trs:ident, $quat:ident, $vec3:ident, $base:ident) => {
+        /// Wrapper serializing a transform with the rotation written as Euler
+        /// angles in degrees rather than as a quaternion.
+        ///
+        /// The angles are those accepted by `euler`, i.e. rotations around the
+        /// X, Y, and Z axes composed in `EulerOrder::ZXY`. Converting to and from
+        /// Euler angles introduces small rounding errors, so the rotation of a
+        /// deserialized transform is only approximately equal to the original.
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        pub struct $repr(pub $trs);
+
+        impl From<$trs> for $repr {
+            fn from(trs: $trs) -> Self {
+                $repr(trs)
+            }
+        }
+
+        impl From<$repr> for $trs {
+            fn from(repr: $repr) -> Self {
+                repr.0
+            }
+        }
+
+        impl Serialize for $repr {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let trs = &self.0;
+                let angles = trs.r.to_euler(EulerOrder::ZXY);
+                let repr = EulerRepr {
+                    translation: trs.t.to_array(),
+                    rotation: [
+                        angles.x.to_degrees(),
+                        angles.y.to_degrees(),
+                        angles.z.to_degrees(),
+                    ],
+                    scale: trs.s.to_array(),
+                };
+                repr.serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $repr {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let repr = EulerRepr::<$base>::deserialize(deserializer)?;
+                let [x, y, z] = repr.rotation;
+                let angles = $vec3::new(x.to_radians(), y.to_radians(), z.to_radians());
+                Ok($repr($trs::new(
+                    repr.translation.into(),
+                    $quat::euler_with_order(angles, EulerOrder::ZXY),
+                    repr.scale.into(),
+                )))
+            }
+        }
+    };
+}
+
+impl_trs_euler_repr!(TrsEulerRepr, Trs, Quat, Vec3, f32);
+impl_trs_euler_repr!(DTrsEulerRepr, DTrs, DQuat, DVec3, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn serde_trs_euler_repr() {
+        let trs = Trs::new(
+            vec3!(1.0, -2.0, 3.0),
+            Quat::euler(vec3!(0.5, -0.25, 1.0)),
+            vec3!(2.0, 0.5, 1.5),
+        );
+        let json = serde_json::to_string(&TrsEulerRepr(trs)).unwrap();
+        let repr: TrsEulerRepr = serde_json::from_str(&json).unwrap();
+        approx::assert_relative_eq!(repr.0, trs, epsilon = 1.0e-5);
+
+        let json = r#"{
+            "translation": [0.0, 1.0, 0.0],
+            "rotation": [0.0, 90.0, 0.0],
+            "scale": [1.0, 1.0, 1.0]
+        }"#;
+        let trs: DTrs = serde_json::from_str::<DTrsEulerRepr>(json).unwrap().into();
+        approx::assert_relative_eq!(
+            trs,
+            DTrs::new(
+                dvec3!(0.0, 1.0, 0.0),
+                DQuat::axis_angle(dvec3!(0.0, 1.0, 0.0), std::f64::consts::FRAC_PI_2),
+                dvec3!(1.0),
+            ),
+            epsilon = 1.0e-12
+        );
+        let value = serde_json::to_value(DTrsEulerRepr(trs)).unwrap();
+        approx::assert_relative_eq!(
+            value["rotation"][1].as_f64().unwrap(),
+            90.0,
+            epsilon = 1.0e-12
+        );
+    }
+}
@@ -1,8 +1,32 @@
 use cgmath;
 use std::fmt;
 
-use crate::{DMat4, DQuat, DVec3, Mat4, Quat, Vec3};
+use crate::{DMat3, DMat4, DQuat, DVec3, DVec4, Mat3, Mat4, Quat, Vec3, Vec4};
 use approx::ApproxEq;
+use approx05::AbsDiffEq;
+
+/// Samples a keyframe track sorted by time, interpolating between the
+/// keyframes either side of `time`.
+fn sample_keys<S, T, F>(keys: &[(S, T)], time: S, default: T, interpolate: F) -> T
+where
+    S: cgmath::BaseFloat,
+    T: Copy,
+    F: Fn(T, T, S) -> T,
+{
+    match (keys.first(), keys.last()) {
+        (Some(first), _) if time <= first.0 => first.1,
+        (_, Some(last)) if time >= last.0 => last.1,
+        _ => keys
+            .windows(2)
+            .find(|pair| time < pair[1].0)
+            .map(|pair| {
+                let (t0, a) = pair[0];
+                let (t1, b) = pair[1];
+                interpolate(a, b, (time - t0) / (t1 - t0))
+            })
+            .unwrap_or(default),
+    }
+}
 
 /// Single-precision translation + rotation + non-uniform scale transform.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -19,7 +43,13 @@ pub struct Trs {
 
 impl fmt::Display for Trs {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", (self.t, self.r, self.s))
+        f.write_str("(")?;
+        fmt::Display::fmt(&self.t, f)?;
+        f.write_str(", ")?;
+        fmt::Display::fmt(&self.r, f)?;
+        f.write_str(", ")?;
+        fmt::Display::fmt(&self.s, f)?;
+        f.write_str(")")
     }
 }
 
@@ -44,6 +74,36 @@ impl Trs {
         }
     }
 
+    /// Pure translation constructor.
+    pub fn from_translation(t: Vec3) -> Self {
+        Trs::identity().with_translation(t)
+    }
+
+    /// Pure rotation constructor.
+    pub fn from_rotation(r: Quat) -> Self {
+        Trs::identity().with_rotation(r)
+    }
+
+    /// Pure scale constructor.
+    pub fn from_scale(s: Vec3) -> Self {
+        Trs::identity().with_scale(s)
+    }
+
+    /// Returns a copy of the transform with the translation replaced by `t`.
+    pub fn with_translation(self, t: Vec3) -> Self {
+        Trs { t, ..self }
+    }
+
+    /// Returns a copy of the transform with the rotation replaced by `r`.
+    pub fn with_rotation(self, r: Quat) -> Self {
+        Trs { r, ..self }
+    }
+
+    /// Returns a copy of the transform with the scale replaced by `s`.
+    pub fn with_scale(self, s: Vec3) -> Self {
+        Trs { s, ..self }
+    }
+
     /// Returns the equivalent matrix representation for this transform.
     pub fn matrix(&self) -> Mat4 {
         let t =
@@ -55,6 +115,198 @@ impl Trs {
         let m: [[f32; 4]; 4] = (t * r * s).into();
         Mat4::from(m)
     }
+
+    /// Returns the equivalent matrix representation for this transform.
+    ///
+    /// Produces the same result as `matrix` but computes the matrix elements
+    /// directly rather than by multiplying the individual translation, rotation,
+    /// and scale matrices. The rotation quaternion is assumed to be normalized.
+    pub fn matrix_fast(&self) -> Mat4 {
+        let (x, y, z, w) = (self.r.x, self.r.y, self.r.z, self.r.s);
+        let (x2, y2, z2) = (x + x, y + y, z + z);
+        let (xx2, xy2, xz2) = (x2 * x, x2 * y, x2 * z);
+        let (yy2, yz2, zz2) = (y2 * y, y2 * z, z2 * z);
+        let (wx2, wy2, wz2) = (x2 * w, y2 * w, z2 * w);
+        Mat4::new(
+            (1.0 - yy2 - zz2) * self.s.x,
+            (xy2 + wz2) * self.s.x,
+            (xz2 - wy2) * self.s.x,
+            0.0,
+            (xy2 - wz2) * self.s.y,
+            (1.0 - xx2 - zz2) * self.s.y,
+            (yz2 + wx2) * self.s.y,
+            0.0,
+            (xz2 + wy2) * self.s.z,
+            (yz2 - wx2) * self.s.z,
+            (1.0 - xx2 - yy2) * self.s.z,
+            0.0,
+            self.t.x,
+            self.t.y,
+            self.t.z,
+            1.0,
+        )
+    }
+
+    /// Returns the inverse of the matrix representation of this transform.
+    ///
+    /// The inverse is built directly from the inverted scale, rotation, and
+    /// translation rather than by numerically inverting `matrix`. The rotation
+    /// quaternion is assumed to be normalized and the scale factors non-zero.
+    pub fn inverse_matrix(&self) -> Mat4 {
+        let inverse_scale = Mat3::new(
+            1.0 / self.s.x,
+            0.0,
+            0.0,
+            0.0,
+            1.0 / self.s.y,
+            0.0,
+            0.0,
+            0.0,
+            1.0 / self.s.z,
+        );
+        let linear = inverse_scale * Mat3::from(self.r).transpose();
+        let t = linear * -self.t;
+        let mut m = Mat4::from(linear);
+        m.m30 = t.x;
+        m.m31 = t.y;
+        m.m32 = t.z;
+        m
+    }
+
+    /// Returns the transformation of the homogeneous vector `v`.
+    ///
+    /// Translation contributes in proportion to `v.w`, hence `w = 1` transforms
+    /// a point and `w = 0` a direction. Equivalent to `self.matrix() * v`.
+    pub fn transform_vec4(&self, v: Vec4) -> Vec4 {
+        let s = self.s;
+        let p = self.r.rotate(vec3!(v.x * s.x, v.y * s.y, v.z * s.z)) + self.t * v.w;
+        vec4!(p, v.w)
+    }
+
+    /// Returns the transformation of the point `p`, equivalent to
+    /// `self.matrix().transform_point(p)`.
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        self.transform_vector(p) + self.t
+    }
+
+    /// Returns the transformation of the direction vector `v`, ignoring
+    /// translation.
+    pub fn transform_vector(&self, v: Vec3) -> Vec3 {
+        let s = self.s;
+        self.r.rotate(vec3!(v.x * s.x, v.y * s.y, v.z * s.z))
+    }
+
+    /// Returns the inverse transform.
+    ///
+    /// The inverse of a rotation combined with a non-uniform scale is a shear,
+    /// which cannot be represented exactly as a TRS. In that case the result
+    /// only approximates the inverse: it maps the translation of `self` back to
+    /// the origin, but the rotation and scale are merely inverted separately.
+    /// Use `inverse_matrix` or the `world_to_local_*` functions for an exact
+    /// inverse. The rotation quaternion is assumed to be normalized and the
+    /// scale factors non-zero.
+    pub fn inverse(&self) -> Self {
+        let r = self.r.conjugate();
+        let s = vec3!(1.0 / self.s.x, 1.0 / self.s.y, 1.0 / self.s.z);
+        let t = r.rotate(vec3!(self.t.x * s.x, self.t.y * s.y, self.t.z * s.z));
+        Trs { t: -t, r, s }
+    }
+
+    /// Returns the transformation of the point `p` from the local space of this
+    /// transform to the parent (world) space, as per `transform_point`.
+    pub fn local_to_world_point(&self, p: Vec3) -> Vec3 {
+        self.transform_point(p)
+    }
+
+    /// Returns the transformation of the direction vector `v` from the local
+    /// space of this transform to the parent (world) space, ignoring
+    /// translation, as per `transform_vector`.
+    pub fn local_to_world_vector(&self, v: Vec3) -> Vec3 {
+        self.transform_vector(v)
+    }
+
+    /// Returns the transformation of the point `p` from the parent (world)
+    /// space to the local space of this transform, i.e. the inverse of
+    /// `local_to_world_point`.
+    ///
+    /// The rotation quaternion is assumed to be normalized and the scale
+    /// factors non-zero.
+    pub fn world_to_local_point(&self, p: Vec3) -> Vec3 {
+        self.world_to_local_vector(p - self.t)
+    }
+
+    /// Returns the transformation of the direction vector `v` from the parent
+    /// (world) space to the local space of this transform, i.e. the inverse of
+    /// `local_to_world_vector`.
+    ///
+    /// Unlike `self.inverse().transform_vector(v)`, this is exact even when
+    /// the scale is non-uniform. The rotation quaternion is assumed to be
+    /// normalized and the scale factors non-zero.
+    pub fn world_to_local_vector(&self, v: Vec3) -> Vec3 {
+        let (v, s) = (self.r.conjugate().rotate(v), self.s);
+        vec3!(v.x / s.x, v.y / s.y, v.z / s.z)
+    }
+
+    /// Decomposes an affine transformation matrix into its translation,
+    /// rotation, and non-uniform scale components.
+    ///
+    /// If the matrix has a negative determinant (i.e. contains a mirror) then
+    /// the X axis scale factor is negated. If any scale factor is zero then the
+    /// rotation cannot be recovered and is set to the identity.
+    pub fn from_matrix(m: Mat4) -> Self {
+        let (t, r, s) = m.decompose();
+        Trs { t, r, s }
+    }
+
+    /// Returns the interpolation between two transforms.
+    ///
+    /// Translation and scale are interpolated linearly and rotation is
+    /// interpolated spherically. Returns `self` for `t = 0` and `other` for
+    /// `t = 1` exactly, even where the spherical interpolation would yield the
+    /// negated or renormalized rotation quaternion.
+    pub fn lerp(self, other: Trs, t: f32) -> Self {
+        if t == 0.0 {
+            return self;
+        } else if t == 1.0 {
+            return other;
+        }
+        Trs {
+            t: self.t * (1.0 - t) + other.t * t,
+            r: self.r.slerp(other.r, t),
+            s: self.s * (1.0 - t) + other.s * t,
+        }
+    }
+
+    /// Samples a transform from separate translation, rotation, and scale
+    /// keyframe tracks at the given time.
+    ///
+    /// Each track is a sequence of `(time, value)` pairs sorted by time.
+    /// Translation and scale are interpolated linearly and rotation is
+    /// interpolated spherically. Times outside a track are clamped to its
+    /// first or last keyframe, and empty tracks yield the identity component.
+    pub fn sample(
+        translation_keys: &[(f32, Vec3)],
+        rotation_keys: &[(f32, Quat)],
+        scale_keys: &[(f32, Vec3)],
+        time: f32,
+    ) -> Self {
+        let identity = Trs::identity();
+        Trs {
+            t: sample_keys(translation_keys, time, identity.t, |a, b, t| {
+                a * (1.0 - t) + b * t
+            }),
+            r: sample_keys(rotation_keys, time, identity.r, |a, b, t| a.slerp(b, t)),
+            s: sample_keys(scale_keys, time, identity.s, |a, b, t| {
+                a * (1.0 - t) + b * t
+            }),
+        }
+    }
+}
+
+impl From<DTrs> for Trs {
+    fn from(arg: DTrs) -> Self {
+        Trs::new(arg.t.into(), arg.r.into(), arg.s.into())
+    }
 }
 
 impl ApproxEq for Trs {
@@ -90,6 +342,20 @@ impl ApproxEq for Trs {
     }
 }
 
+impl AbsDiffEq for Trs {
+    type Epsilon = f32;
+
+    fn default_epsilon() -> Self::Epsilon {
+        f32::EPSILON
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.t.abs_diff_eq(&other.t, epsilon)
+            && self.r.abs_diff_eq(&other.r, epsilon)
+            && self.s.abs_diff_eq(&other.s, epsilon)
+    }
+}
+
 /// Double-precision translation + rotation + non-uniform scale transform.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct DTrs {
@@ -105,7 +371,13 @@ pub struct DTrs {
 
 impl fmt::Display for DTrs {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", (self.t, self.r, self.s))
+        f.write_str("(")?;
+        fmt::Display::fmt(&self.t, f)?;
+        f.write_str(", ")?;
+        fmt::Display::fmt(&self.r, f)?;
+        f.write_str(", ")?;
+        fmt::Display::fmt(&self.s, f)?;
+        f.write_str(")")
     }
 }
 
@@ -130,6 +402,36 @@ impl DTrs {
         }
     }
 
+    /// Pure translation constructor.
+    pub fn from_translation(t: DVec3) -> Self {
+        DTrs::identity().with_translation(t)
+    }
+
+    /// Pure rotation constructor.
+    pub fn from_rotation(r: DQuat) -> Self {
+        DTrs::identity().with_rotation(r)
+    }
+
+    /// Pure scale constructor.
+    pub fn from_scale(s: DVec3) -> Self {
+        DTrs::identity().with_scale(s)
+    }
+
+    /// Returns a copy of the transform with the translation replaced by `t`.
+    pub fn with_translation(self, t: DVec3) -> Self {
+        DTrs { t, ..self }
+    }
+
+    /// Returns a copy of the transform with the rotation replaced by `r`.
+    pub fn with_rotation(self, r: DQuat) -> Self {
+        DTrs { r, ..self }
+    }
+
+    /// Returns a copy of the transform with the scale replaced by `s`.
+    pub fn with_scale(self, s: DVec3) -> Self {
+        DTrs { s, ..self }
+    }
+
     /// Returns the equivalent matrix representation for this transform.
     pub fn matrix(&self) -> DMat4 {
         let t =
@@ -141,6 +443,198 @@ impl DTrs {
         let m: [[f64; 4]; 4] = (t * r * s).into();
         DMat4::from(m)
     }
+
+    /// Returns the equivalent matrix representation for this transform.
+    ///
+    /// Produces the same result as `matrix` but computes the matrix elements
+    /// directly rather than by multiplying the individual translation, rotation,
+    /// and scale matrices. The rotation quaternion is assumed to be normalized.
+    pub fn matrix_fast(&self) -> DMat4 {
+        let (x, y, z, w) = (self.r.x, self.r.y, self.r.z, self.r.s);
+        let (x2, y2, z2) = (x + x, y + y, z + z);
+        let (xx2, xy2, xz2) = (x2 * x, x2 * y, x2 * z);
+        let (yy2, yz2, zz2) = (y2 * y, y2 * z, z2 * z);
+        let (wx2, wy2, wz2) = (x2 * w, y2 * w, z2 * w);
+        DMat4::new(
+            (1.0 - yy2 - zz2) * self.s.x,
+            (xy2 + wz2) * self.s.x,
+            (xz2 - wy2) * self.s.x,
+            0.0,
+            (xy2 - wz2) * self.s.y,
+            (1.0 - xx2 - zz2) * self.s.y,
+            (yz2 + wx2) * self.s.y,
+            0.0,
+            (xz2 + wy2) * self.s.z,
+            (yz2 - wx2) * self.s.z,
+            (1.0 - xx2 - yy2) * self.s.z,
+            0.0,
+            self.t.x,
+            self.t.y,
+            self.t.z,
+            1.0,
+        )
+    }
+
+    /// Returns the inverse of the matrix representation of this transform.
+    ///
+    /// The inverse is built directly from the inverted scale, rotation, and
+    /// translation rather than by numerically inverting `matrix`. The rotation
+    /// quaternion is assumed to be normalized and the scale factors non-zero.
+    pub fn inverse_matrix(&self) -> DMat4 {
+        let inverse_scale = DMat3::new(
+            1.0 / self.s.x,
+            0.0,
+            0.0,
+            0.0,
+            1.0 / self.s.y,
+            0.0,
+            0.0,
+            0.0,
+            1.0 / self.s.z,
+        );
+        let linear = inverse_scale * DMat3::from(self.r).transpose();
+        let t = linear * -self.t;
+        let mut m = DMat4::from(linear);
+        m.m30 = t.x;
+        m.m31 = t.y;
+        m.m32 = t.z;
+        m
+    }
+
+    /// Returns the transformation of the homogeneous vector `v`.
+    ///
+    /// Translation contributes in proportion to `v.w`, hence `w = 1` transforms
+    /// a point and `w = 0` a direction. Equivalent to `self.matrix() * v`.
+    pub fn transform_vec4(&self, v: DVec4) -> DVec4 {
+        let s = self.s;
+        let p = self.r.rotate(dvec3!(v.x * s.x, v.y * s.y, v.z * s.z)) + self.t * v.w;
+        dvec4!(p, v.w)
+    }
+
+    /// Returns the transformation of the point `p`, equivalent to
+    /// `self.matrix().transform_point(p)`.
+    pub fn transform_point(&self, p: DVec3) -> DVec3 {
+        self.transform_vector(p) + self.t
+    }
+
+    /// Returns the transformation of the direction vector `v`, ignoring
+    /// translation.
+    pub fn transform_vector(&self, v: DVec3) -> DVec3 {
+        let s = self.s;
+        self.r.rotate(dvec3!(v.x * s.x, v.y * s.y, v.z * s.z))
+    }
+
+    /// Returns the inverse transform.
+    ///
+    /// The inverse of a rotation combined with a non-uniform scale is a shear,
+    /// which cannot be represented exactly as a TRS. In that case the result
+    /// only approximates the inverse: it maps the translation of `self` back to
+    /// the origin, but the rotation and scale are merely inverted separately.
+    /// Use `inverse_matrix` or the `world_to_local_*` functions for an exact
+    /// inverse. The rotation quaternion is assumed to be normalized and the
+    /// scale factors non-zero.
+    pub fn inverse(&self) -> Self {
+        let r = self.r.conjugate();
+        let s = dvec3!(1.0 / self.s.x, 1.0 / self.s.y, 1.0 / self.s.z);
+        let t = r.rotate(dvec3!(self.t.x * s.x, self.t.y * s.y, self.t.z * s.z));
+        DTrs { t: -t, r, s }
+    }
+
+    /// Returns the transformation of the point `p` from the local space of this
+    /// transform to the parent (world) space, as per `transform_point`.
+    pub fn local_to_world_point(&self, p: DVec3) -> DVec3 {
+        self.transform_point(p)
+    }
+
+    /// Returns the transformation of the direction vector `v` from the local
+    /// space of this transform to the parent (world) space, ignoring
+    /// translation, as per `transform_vector`.
+    pub fn local_to_world_vector(&self, v: DVec3) -> DVec3 {
+        self.transform_vector(v)
+    }
+
+    /// Returns the transformation of the point `p` from the parent (world)
+    /// space to the local space of this transform, i.e. the inverse of
+    /// `local_to_world_point`.
+    ///
+    /// The rotation quaternion is assumed to be normalized and the scale
+    /// factors non-zero.
+    pub fn world_to_local_point(&self, p: DVec3) -> DVec3 {
+        self.world_to_local_vector(p - self.t)
+    }
+
+    /// Returns the transformation of the direction vector `v` from the parent
+    /// (world) space to the local space of this transform, i.e. the inverse of
+    /// `local_to_world_vector`.
+    ///
+    /// Unlike `self.inverse().transform_vector(v)`, this is exact even when
+    /// the scale is non-uniform. The rotation quaternion is assumed to be
+    /// normalized and the scale factors non-zero.
+    pub fn world_to_local_vector(&self, v: DVec3) -> DVec3 {
+        let (v, s) = (self.r.conjugate().rotate(v), self.s);
+        dvec3!(v.x / s.x, v.y / s.y, v.z / s.z)
+    }
+
+    /// Decomposes an affine transformation matrix into its translation,
+    /// rotation, and non-uniform scale components.
+    ///
+    /// If the matrix has a negative determinant (i.e. contains a mirror) then
+    /// the X axis scale factor is negated. If any scale factor is zero then the
+    /// rotation cannot be recovered and is set to the identity.
+    pub fn from_matrix(m: DMat4) -> Self {
+        let (t, r, s) = m.decompose();
+        DTrs { t, r, s }
+    }
+
+    /// Returns the interpolation between two transforms.
+    ///
+    /// Translation and scale are interpolated linearly and rotation is
+    /// interpolated spherically. Returns `self` for `t = 0` and `other` for
+    /// `t = 1` exactly, even where the spherical interpolation would yield the
+    /// negated or renormalized rotation quaternion.
+    pub fn lerp(self, other: DTrs, t: f64) -> Self {
+        if t == 0.0 {
+            return self;
+        } else if t == 1.0 {
+            return other;
+        }
+        DTrs {
+            t: self.t * (1.0 - t) + other.t * t,
+            r: self.r.slerp(other.r, t),
+            s: self.s * (1.0 - t) + other.s * t,
+        }
+    }
+
+    /// Samples a transform from separate translation, rotation, and scale
+    /// keyframe tracks at the given time.
+    ///
+    /// Each track is a sequence of `(time, value)` pairs sorted by time.
+    /// Translation and scale are interpolated linearly and rotation is
+    /// interpolated spherically. Times outside a track are clamped to its
+    /// first or last keyframe, and empty tracks yield the identity component.
+    pub fn sample(
+        translation_keys: &[(f64, DVec3)],
+        rotation_keys: &[(f64, DQuat)],
+        scale_keys: &[(f64, DVec3)],
+        time: f64,
+    ) -> Self {
+        let identity = DTrs::identity();
+        DTrs {
+            t: sample_keys(translation_keys, time, identity.t, |a, b, t| {
+                a * (1.0 - t) + b * t
+            }),
+            r: sample_keys(rotation_keys, time, identity.r, |a, b, t| a.slerp(b, t)),
+            s: sample_keys(scale_keys, time, identity.s, |a, b, t| {
+                a * (1.0 - t) + b * t
+            }),
+        }
+    }
+}
+
+impl From<Trs> for DTrs {
+    fn from(arg: Trs) -> Self {
+        DTrs::new(arg.t.into(), arg.r.into(), arg.s.into())
+    }
 }
 
 impl ApproxEq for DTrs {
@@ -175,3 +669,341 @@ impl ApproxEq for DTrs {
             && self.s.ulps_eq(&other.s, epsilon, max_ulps)
     }
 }
+
+impl AbsDiffEq for DTrs {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> Self::Epsilon {
+        f64::EPSILON
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.t.abs_diff_eq(&other.t, epsilon)
+            && self.r.abs_diff_eq(&other.r, epsilon)
+            && self.s.abs_diff_eq(&other.s, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn trs_from_matrix() {
+        let trs = Trs::new(
+            vec3!(1.0, -2.0, 3.0),
+            quat!(1.0, 2.0, 3.0; 0.75),
+            vec3!(2.0, 0.5, 3.0),
+        );
+        approx::assert_relative_eq!(Trs::from_matrix(trs.matrix()), trs, epsilon = 1.0e-5);
+
+        let dtrs = DTrs::new(
+            dvec3!(1.0, -2.0, 3.0),
+            dquat!(1.0, 2.0, 3.0; 0.75),
+            dvec3!(2.0, 0.5, 3.0),
+        );
+        approx::assert_relative_eq!(DTrs::from_matrix(dtrs.matrix()), dtrs, epsilon = 1.0e-12);
+    }
+
+    #[test]
+    pub fn trs_from_mirrored_matrix() {
+        let trs = Trs::new(
+            vec3!(1.0, -2.0, 3.0),
+            quat!(0.0, 1.0, 0.0; 0.5),
+            vec3!(-2.0, 0.5, 3.0),
+        );
+        let m = trs.matrix();
+        let decomposed = Trs::from_matrix(m);
+        assert!(decomposed.s.x < 0.0);
+        approx::assert_relative_eq!(decomposed.matrix(), m, epsilon = 1.0e-5);
+    }
+
+    #[test]
+    pub fn trs_from_matrix_zero_scale() {
+        let trs = Trs::new(
+            vec3!(1.0, -2.0, 3.0),
+            quat!(1.0, 2.0, 3.0; 0.75),
+            vec3!(2.0, 0.5, 0.0),
+        );
+        let decomposed = Trs::from_matrix(trs.matrix());
+        assert_eq!(decomposed.r, quat!());
+        assert!(decomposed.r.check_invariants().is_ok());
+        approx::assert_relative_eq!(decomposed.t, trs.t);
+        approx::assert_relative_eq!(decomposed.s, trs.s, epsilon = 1.0e-6);
+    }
+
+    #[test]
+    pub fn trs_lerp() {
+        let a = Trs::new(vec3!(1.0, 2.0, 3.0), quat!(1.0, 0.0, 0.0; 0.5), vec3!(1.0));
+        let b = Trs::new(vec3!(-3.0, 0.0, 1.0), quat!(0.0, 1.0, 1.0; 1.5), vec3!(2.0));
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+
+        let mid = a.lerp(b, 0.5);
+        let r = mid.r;
+        let length = (r.x * r.x + r.y * r.y + r.z * r.z + r.s * r.s).sqrt();
+        approx::assert_relative_eq!(length, 1.0, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(mid.t, vec3!(-1.0, 1.0, 2.0));
+        approx::assert_relative_eq!(mid.s, vec3!(1.5));
+
+        let a = DTrs::new(
+            dvec3!(1.0, 2.0, 3.0),
+            dquat!(1.0, 0.0, 0.0; 0.5),
+            dvec3!(1.0),
+        );
+        let b = DTrs::new(
+            dvec3!(-3.0, 0.0, 1.0),
+            dquat!(0.0, 1.0, 1.0; 1.5),
+            dvec3!(2.0),
+        );
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+
+        // Rotations with a negative dot product, and nearly identical ones.
+        let a = Trs::from_rotation(quat!(0.0, 1.0, 0.0; 0.5));
+        for b in [
+            Trs::from_rotation(quat!(0.0, 1.0, 0.0; 6.0)),
+            Trs::from_rotation(quat!(0.0, 1.0, 0.0; 0.5001)),
+        ] {
+            assert_eq!(a.lerp(b, 0.0), a);
+            assert_eq!(a.lerp(b, 1.0), b);
+        }
+        let b = DTrs::from_rotation(dquat!(1.0, 0.0, 0.0; 6.0));
+        assert_eq!(DTrs::identity().lerp(b, 1.0), b);
+    }
+
+    #[test]
+    pub fn trs_sample() {
+        let translation_keys = [(0.0, vec3!(0.0)), (1.0, vec3!(2.0, 0.0, 0.0))];
+        let rotation_keys = [
+            (0.5, quat!()),
+            (1.5, quat!(0.0, 1.0, 0.0; 1.0)),
+            (2.5, quat!(0.0, 1.0, 0.0; 2.0)),
+        ];
+        let scale_keys = [(0.0, vec3!(1.0)), (0.25, vec3!(2.0)), (1.25, vec3!(4.0))];
+
+        let trs = Trs::sample(&translation_keys, &rotation_keys, &scale_keys, 0.75);
+        approx::assert_relative_eq!(trs.t, vec3!(1.5, 0.0, 0.0));
+        approx::assert_relative_eq!(trs.r, quat!(0.0, 1.0, 0.0; 0.25), epsilon = 1.0e-6);
+        approx::assert_relative_eq!(trs.s, vec3!(3.0));
+
+        let trs = Trs::sample(&translation_keys, &rotation_keys, &[], 3.0);
+        approx::assert_relative_eq!(trs.t, vec3!(2.0, 0.0, 0.0));
+        approx::assert_relative_eq!(trs.r, quat!(0.0, 1.0, 0.0; 2.0));
+        approx::assert_relative_eq!(trs.s, vec3!(1.0));
+
+        let trs = DTrs::sample(&[(0.0, dvec3!(1.0)), (2.0, dvec3!(3.0))], &[], &[], 0.5);
+        approx::assert_relative_eq!(trs, DTrs::new(dvec3!(1.5), dquat!(), dvec3!(1.0)));
+    }
+
+    #[test]
+    pub fn trs_matrix_fast() {
+        let trs = Trs::new(
+            vec3!(1.0, -2.0, 3.0),
+            quat!(1.0, 2.0, 3.0; 0.75),
+            vec3!(2.0, 0.5, -3.0),
+        );
+        approx::assert_ulps_eq!(trs.matrix_fast(), trs.matrix());
+
+        let dtrs = DTrs::new(
+            dvec3!(1.0, -2.0, 3.0),
+            dquat!(1.0, 2.0, 3.0; 0.75),
+            dvec3!(2.0, 0.5, -3.0),
+        );
+        approx::assert_ulps_eq!(dtrs.matrix_fast(), dtrs.matrix());
+    }
+
+    #[test]
+    pub fn trs_abs_diff_eq() {
+        let a = Trs::new(vec3!(1.0, 2.0, 3.0), Quat::identity(), vec3!(1.0, 1.0, 1.0));
+        let mut b = a;
+        b.t.z += 1.0e-7;
+        approx05::assert_abs_diff_eq!(a, b, epsilon = 1.0e-6);
+        b.s.x = 2.0;
+        approx05::assert_abs_diff_ne!(a, b, epsilon = 1.0e-6);
+    }
+
+    #[test]
+    pub fn trs_precision_conversion() {
+        let a = Trs::new(
+            vec3!(1.5, -2.0, 3.25),
+            quat!(1.0, 2.0, -3.0; 0.7),
+            vec3!(2.0, 0.5, 1.0),
+        );
+        let b = DTrs::from(a);
+        assert_eq!(b.t, dvec3!(1.5, -2.0, 3.25));
+        assert_eq!(b.r, DQuat::from(a.r));
+        assert_eq!(Trs::from(b), a);
+
+        let c = dtrs!(
+            dvec3!(0.1, 0.2, 0.3),
+            dquat!(0.0, 1.0, 0.0; 1.3),
+            dvec3!(1.0)
+        );
+        approx::assert_relative_eq!(DTrs::from(Trs::from(c)), c, epsilon = 1.0e-6);
+    }
+
+    #[test]
+    pub fn trs_inverse_matrix() {
+        let trs = Trs::new(
+            vec3!(1.0, -2.0, 3.5),
+            quat!(1.0, 1.0, -2.0; 0.8),
+            vec3!(2.0, 0.5, 3.0),
+        );
+        approx::assert_relative_eq!(
+            trs.inverse_matrix(),
+            trs.matrix().inverse(),
+            epsilon = 1.0e-5
+        );
+        approx::assert_relative_eq!(
+            trs.inverse_matrix() * trs.matrix(),
+            Mat4::identity(),
+            epsilon = 1.0e-5
+        );
+
+        let trs = DTrs::new(
+            dvec3!(-4.0, 0.0, 1.0),
+            dquat!(0.0, 1.0, 0.0; -2.3),
+            dvec3!(0.25, 4.0, -1.0),
+        );
+        approx::assert_relative_eq!(
+            trs.inverse_matrix(),
+            trs.matrix().inverse(),
+            epsilon = 1.0e-12
+        );
+    }
+
+    #[test]
+    pub fn trs_with() {
+        let r = quat!(0.0, 1.0, 0.0; 0.5);
+        let trs = Trs::identity()
+            .with_translation(vec3!(1.0, 2.0, 3.0))
+            .with_rotation(r)
+            .with_scale(vec3!(2.0, 2.0, 0.5));
+        assert_eq!(trs, Trs::new(vec3!(1.0, 2.0, 3.0), r, vec3!(2.0, 2.0, 0.5)));
+        assert_eq!(trs.with_scale(vec3!(1.0)).t, trs.t);
+
+        let trs = DTrs::identity()
+            .with_scale(dvec3!(3.0))
+            .with_translation(dvec3!(-1.0));
+        assert_eq!(trs, DTrs::new(dvec3!(-1.0), DQuat::identity(), dvec3!(3.0)));
+        assert_eq!(trs.with_rotation(dquat!(1.0, 0.0, 0.0; 1.0)).s, dvec3!(3.0));
+    }
+
+    #[test]
+    pub fn trs_display_precision() {
+        let trs = Trs::new(vec3!(1.0, 2.0, 3.0), Quat::identity(), vec3!(0.5));
+        assert_eq!(
+            format!("{}", trs),
+            "((1.0, 2.0, 3.0), (0, 0, 0; 1), (0.5, 0.5, 0.5))"
+        );
+        assert_eq!(
+            format!("{:.1}", DTrs::from(trs)),
+            "((1.0, 2.0, 3.0), (0.0, 0.0, 0.0; 1.0), (0.5, 0.5, 0.5))"
+        );
+    }
+
+    #[test]
+    pub fn trs_transform_vec4() {
+        let trs = Trs::new(
+            vec3!(1.0, -2.0, 3.0),
+            quat!(1.0, 2.0, 3.0; 0.9),
+            vec3!(2.0, 0.5, 1.5),
+        );
+        for v in [vec4!(0.3, 1.0, -2.0, 1.0), vec4!(0.3, 1.0, -2.0, 0.0)] {
+            approx::assert_relative_eq!(trs.transform_vec4(v), trs.matrix() * v, epsilon = 1.0e-5);
+        }
+
+        let trs = DTrs::from(trs);
+        for v in [dvec4!(-4.0, 0.5, 2.0, 1.0), dvec4!(-4.0, 0.5, 2.0, 0.0)] {
+            approx::assert_relative_eq!(trs.transform_vec4(v), trs.matrix() * v, epsilon = 1.0e-12);
+        }
+    }
+
+    #[test]
+    pub fn trs_world_local() {
+        let trs = Trs::new(
+            vec3!(1.0, -2.0, 3.0),
+            quat!(1.0, 2.0, 3.0; 0.9),
+            vec3!(2.0, 0.5, 1.5),
+        );
+        let p = vec3!(0.3, 1.0, -2.0);
+        let world = trs.local_to_world_point(p);
+        approx::assert_relative_eq!(world, trs.matrix().transform_point(p), epsilon = 1.0e-5);
+        approx::assert_relative_eq!(trs.world_to_local_point(world), p, epsilon = 1.0e-5);
+        let world = trs.local_to_world_vector(p);
+        approx::assert_relative_eq!(world, trs.matrix().transform_vector(p), epsilon = 1.0e-5);
+        approx::assert_relative_eq!(trs.world_to_local_vector(world), p, epsilon = 1.0e-5);
+
+        let trs = DTrs::new(
+            dvec3!(5.0, 0.0, -1.0),
+            dquat!(0.0, 1.0, 1.0; 2.5),
+            dvec3!(0.5, 3.0, 2.0),
+        );
+        let p = dvec3!(-4.0, 0.5, 2.0);
+        approx::assert_relative_eq!(
+            trs.world_to_local_point(trs.local_to_world_point(p)),
+            p,
+            epsilon = 1.0e-12
+        );
+        approx::assert_relative_eq!(
+            trs.world_to_local_point(p),
+            trs.inverse_matrix().transform_point(p),
+            epsilon = 1.0e-12
+        );
+    }
+
+    #[test]
+    pub fn trs_inverse() {
+        let trs = Trs::new(vec3!(1.0, -2.0, 3.0), quat!(1.0, 2.0, 3.0; 0.9), vec3!(2.0));
+        let inverse = trs.inverse();
+        approx::assert_relative_eq!(inverse.matrix(), trs.inverse_matrix(), epsilon = 1.0e-5);
+        let p = vec3!(0.3, 1.0, -2.0);
+        approx::assert_relative_eq!(
+            inverse.transform_point(trs.transform_point(p)),
+            p,
+            epsilon = 1.0e-5
+        );
+        approx::assert_relative_eq!(inverse.inverse(), trs, epsilon = 1.0e-5);
+
+        // Non-uniform scale without rotation is still exactly invertible.
+        let trs = DTrs::new(dvec3!(5.0, 0.0, -1.0), dquat!(), dvec3!(0.5, 3.0, 2.0));
+        approx::assert_relative_eq!(
+            trs.inverse().matrix(),
+            trs.inverse_matrix(),
+            epsilon = 1.0e-12
+        );
+
+        // Otherwise only the translation is mapped back exactly.
+        let trs = trs.with_rotation(dquat!(0.0, 1.0, 1.0; 2.5));
+        approx::assert_relative_eq!(
+            trs.inverse().transform_point(trs.t),
+            dvec3!(),
+            epsilon = 1.0e-12
+        );
+    }
+
+    #[test]
+    pub fn trs_single_component_constructors() {
+        let identity = Trs::identity();
+        let t = vec3!(1.0, 2.0, 3.0);
+        let r = quat!(0.0, 1.0, 0.0; 0.5);
+        let s = vec3!(2.0, 3.0, 4.0);
+        assert_eq!(
+            Trs::from_translation(t),
+            Trs::new(t, identity.r, identity.s)
+        );
+        assert_eq!(Trs::from_rotation(r), Trs::new(identity.t, r, identity.s));
+        assert_eq!(Trs::from_scale(s), Trs::new(identity.t, identity.r, s));
+        assert_eq!(
+            Trs::from_translation(t).with_scale(s),
+            Trs::new(t, identity.r, s)
+        );
+
+        let identity = DTrs::identity();
+        let r = dquat!(1.0, 0.0, 0.0; 1.5);
+        assert_eq!(DTrs::from_translation(dvec3!(5.0)).t, dvec3!(5.0));
+        assert_eq!(DTrs::from_rotation(r), identity.with_rotation(r));
+        assert_eq!(DTrs::from_scale(dvec3!(0.5)).r, identity.r);
+    }
+}
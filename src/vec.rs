@@ -1,6 +1,8 @@
+use crate::scalar;
 use approx::ApproxEq;
+use approx05::AbsDiffEq;
 use cgmath;
-use std::{fmt, mem, ops};
+use std::{cmp, fmt, mem, ops};
 
 /// Single-precision 2D vector.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
@@ -12,13 +14,44 @@ pub struct Vec2 {
 
 impl Vec2 {
     /// Full constructor.
-    pub fn new(x: f32, y: f32) -> Self {
+    pub const fn new(x: f32, y: f32) -> Self {
         Vec2 { x, y }
     }
 
     /// Zero constructor.
-    pub fn zero() -> Self {
-        Default::default()
+    pub const fn zero() -> Self {
+        Self::new(0.0, 0.0)
+    }
+
+    /// Returns a 3D vector with the given Z component.
+    pub fn extend(self, z: f32) -> Vec3 {
+        Vec3::new(self.x, self.y, z)
+    }
+
+    /// Returns the polar coordinates `(radius, angle)` of the vector.
+    ///
+    /// The angle is measured counter-clockwise from the positive X axis, in the
+    /// range `[-π, π]`, and is zero for the zero vector.
+    pub fn to_polar(self) -> (f32, f32) {
+        (self.x.hypot(self.y), self.y.atan2(self.x))
+    }
+
+    /// Constructor from polar coordinates, as per `to_polar`.
+    pub fn from_polar(radius: f32, angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(radius * cos, radius * sin)
+    }
+
+    /// Returns the bit patterns of the components packed into a single integer,
+    /// with X in the least significant bits, for use as an exact hash key.
+    ///
+    /// Components are compared by `f32::to_bits`, hence `0.0` and `-0.0` give
+    /// different keys, and NaNs give equal keys only if their bits are equal.
+    pub fn hash_bits(self) -> u128 {
+        self.to_array()
+            .iter()
+            .rev()
+            .fold(0, |key, c| key << 32 | u128::from(c.to_bits()))
     }
 }
 
@@ -40,9 +73,16 @@ impl From<DVec2> for Vec2 {
     }
 }
 
+impl From<(f32, f32)> for Vec2 {
+    fn from((x, y): (f32, f32)) -> Self {
+        Self::new(x, y)
+    }
+}
+
 impl fmt::Display for Vec2 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", (self.x, self.y))
+        let components = [self.x, self.y];
+        scalar::fmt_components(f, &components, 2, fmt::Debug::fmt)
     }
 }
 
@@ -57,19 +97,54 @@ pub struct Vec3 {
 
 impl Vec3 {
     /// Full constructor.
-    pub fn new(x: f32, y: f32, z: f32) -> Self {
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
         Vec3 { x, y, z }
     }
 
     /// Zero constructor.
-    pub fn zero() -> Self {
-        Default::default()
+    pub const fn zero() -> Self {
+        Self::new(0.0, 0.0, 0.0)
     }
 
     /// Returns the XY components of the vector.
     pub fn xy(self) -> Vec2 {
         Vec2::new(self.x, self.y)
     }
+
+    /// Returns a 4D vector with the given W component.
+    pub fn extend(self, w: f32) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, w)
+    }
+
+    /// Returns the XY components of the vector, discarding Z.
+    ///
+    /// Equivalent to `xy`.
+    pub fn truncate(self) -> Vec2 {
+        self.xy()
+    }
+
+    /// Returns the vector whose `i`-th component is the component of `self`
+    /// at index `indices[i]`, e.g. `[2, 0, 1]` gives `(z, x, y)`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if any index is out of range.
+    pub fn shuffle(self, indices: [usize; 3]) -> Self {
+        let a = self.to_array();
+        indices.map(|i| a[i]).into()
+    }
+
+    /// Returns the bit patterns of the components packed into a single integer,
+    /// with X in the least significant bits, for use as an exact hash key.
+    ///
+    /// Components are compared by `f32::to_bits`, hence `0.0` and `-0.0` give
+    /// different keys, and NaNs give equal keys only if their bits are equal.
+    pub fn hash_bits(self) -> u128 {
+        self.to_array()
+            .iter()
+            .rev()
+            .fold(0, |key, c| key << 32 | u128::from(c.to_bits()))
+    }
 }
 
 impl From<f32> for Vec3 {
@@ -97,9 +172,16 @@ impl<T: Into<Vec2>> From<(T, f32)> for Vec3 {
     }
 }
 
+impl From<(f32, f32, f32)> for Vec3 {
+    fn from((x, y, z): (f32, f32, f32)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
 impl fmt::Display for Vec3 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", (self.x, self.y, self.z))
+        let components = [self.x, self.y, self.z];
+        scalar::fmt_components(f, &components, 3, fmt::Debug::fmt)
     }
 }
 
@@ -115,13 +197,24 @@ pub struct Vec4 {
 
 impl Vec4 {
     /// Full constructor.
-    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
         Vec4 { x, y, z, w }
     }
 
     /// Zero constructor.
-    pub fn zero() -> Self {
-        Default::default()
+    pub const fn zero() -> Self {
+        Self::new(0.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Homogeneous point constructor, with `w = 1` so that translation applies.
+    pub fn point(xyz: Vec3) -> Self {
+        Self::new(xyz.x, xyz.y, xyz.z, 1.0)
+    }
+
+    /// Homogeneous direction constructor, with `w = 0` so that translation
+    /// does not apply.
+    pub fn direction(xyz: Vec3) -> Self {
+        Self::new(xyz.x, xyz.y, xyz.z, 0.0)
     }
 
     /// Returns the XY components of the vector.
@@ -133,6 +226,72 @@ impl Vec4 {
     pub fn xyz(self) -> Vec3 {
         Vec3::new(self.x, self.y, self.z)
     }
+
+    /// Returns the XYZ components of the vector, discarding W.
+    ///
+    /// Equivalent to `xyz`.
+    pub fn truncate(self) -> Vec3 {
+        self.xyz()
+    }
+
+    /// Returns the vector whose `i`-th component is the component of `self`
+    /// at index `indices[i]`, e.g. `[2, 0, 1]` gives `(z, x, y)`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if any index is out of range.
+    pub fn shuffle(self, indices: [usize; 4]) -> Self {
+        let a = self.to_array();
+        indices.map(|i| a[i]).into()
+    }
+
+    /// Returns the bit patterns of the components packed into a single integer,
+    /// with X in the least significant bits, for use as an exact hash key.
+    ///
+    /// Components are compared by `f32::to_bits`, hence `0.0` and `-0.0` give
+    /// different keys, and NaNs give equal keys only if their bits are equal.
+    pub fn hash_bits(self) -> u128 {
+        self.to_array()
+            .iter()
+            .rev()
+            .fold(0, |key, c| key << 32 | u128::from(c.to_bits()))
+    }
+
+    /// Returns the cross product of the XYZ components of two vectors, with a
+    /// zero W component.
+    pub fn cross(self, rhs: Self) -> Self {
+        let v = self.xyz().cross(rhs.xyz());
+        Self::new(v.x, v.y, v.z, 0.0)
+    }
+
+    /// Interprets the vector as an sRGB-encoded RGBA color and returns the
+    /// equivalent color in linear RGB.
+    ///
+    /// The piecewise sRGB transfer function is applied to the XYZ (RGB)
+    /// components, which are expected to be in the range `[0, 1]`. The W
+    /// (alpha) component is unchanged.
+    pub fn to_linear(self) -> Self {
+        Self::new(
+            scalar::srgb_to_linear(self.x),
+            scalar::srgb_to_linear(self.y),
+            scalar::srgb_to_linear(self.z),
+            self.w,
+        )
+    }
+
+    /// Interprets the vector as a linear RGBA color and returns the equivalent
+    /// sRGB-encoded color.
+    ///
+    /// This is the inverse of `to_linear`. The W (alpha) component is
+    /// unchanged.
+    pub fn to_srgb(self) -> Self {
+        Self::new(
+            scalar::linear_to_srgb(self.x),
+            scalar::linear_to_srgb(self.y),
+            scalar::linear_to_srgb(self.z),
+            self.w,
+        )
+    }
 }
 
 impl From<f32> for Vec4 {
@@ -167,9 +326,16 @@ impl<T: Into<Vec3>> From<(T, f32)> for Vec4 {
     }
 }
 
+impl From<(f32, f32, f32, f32)> for Vec4 {
+    fn from((x, y, z, w): (f32, f32, f32, f32)) -> Self {
+        Self::new(x, y, z, w)
+    }
+}
+
 impl fmt::Display for Vec4 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", (self.x, self.y, self.z, self.w))
+        let components = [self.x, self.y, self.z, self.w];
+        scalar::fmt_components(f, &components, 4, fmt::Debug::fmt)
     }
 }
 
@@ -183,13 +349,32 @@ pub struct DVec2 {
 
 impl DVec2 {
     /// Full constructor.
-    pub fn new(x: f64, y: f64) -> Self {
+    pub const fn new(x: f64, y: f64) -> Self {
         DVec2 { x, y }
     }
 
     /// Zero constructor.
-    pub fn zero() -> Self {
-        Default::default()
+    pub const fn zero() -> Self {
+        Self::new(0.0, 0.0)
+    }
+
+    /// Returns a 3D vector with the given Z component.
+    pub fn extend(self, z: f64) -> DVec3 {
+        DVec3::new(self.x, self.y, z)
+    }
+
+    /// Returns the polar coordinates `(radius, angle)` of the vector.
+    ///
+    /// The angle is measured counter-clockwise from the positive X axis, in the
+    /// range `[-π, π]`, and is zero for the zero vector.
+    pub fn to_polar(self) -> (f64, f64) {
+        (self.x.hypot(self.y), self.y.atan2(self.x))
+    }
+
+    /// Constructor from polar coordinates, as per `to_polar`.
+    pub fn from_polar(radius: f64, angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(radius * cos, radius * sin)
     }
 }
 
@@ -211,9 +396,16 @@ impl From<Vec2> for DVec2 {
     }
 }
 
+impl From<(f64, f64)> for DVec2 {
+    fn from((x, y): (f64, f64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
 impl fmt::Display for DVec2 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", (self.x, self.y))
+        let components = [self.x, self.y];
+        scalar::fmt_components(f, &components, 2, fmt::Debug::fmt)
     }
 }
 
@@ -228,19 +420,42 @@ pub struct DVec3 {
 
 impl DVec3 {
     /// Full constructor.
-    pub fn new(x: f64, y: f64, z: f64) -> Self {
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
         DVec3 { x, y, z }
     }
 
     /// Zero constructor.
-    pub fn zero() -> Self {
-        Default::default()
+    pub const fn zero() -> Self {
+        Self::new(0.0, 0.0, 0.0)
     }
 
     /// Returns the XY components of the vector.
     pub fn xy(self) -> DVec2 {
         DVec2::new(self.x, self.y)
     }
+
+    /// Returns a 4D vector with the given W component.
+    pub fn extend(self, w: f64) -> DVec4 {
+        DVec4::new(self.x, self.y, self.z, w)
+    }
+
+    /// Returns the XY components of the vector, discarding Z.
+    ///
+    /// Equivalent to `xy`.
+    pub fn truncate(self) -> DVec2 {
+        self.xy()
+    }
+
+    /// Returns the vector whose `i`-th component is the component of `self`
+    /// at index `indices[i]`, e.g. `[2, 0, 1]` gives `(z, x, y)`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if any index is out of range.
+    pub fn shuffle(self, indices: [usize; 3]) -> Self {
+        let a = self.to_array();
+        indices.map(|i| a[i]).into()
+    }
 }
 
 impl From<f32> for DVec3 {
@@ -268,9 +483,16 @@ impl<T: Into<DVec2>> From<(T, f64)> for DVec3 {
     }
 }
 
+impl From<(f64, f64, f64)> for DVec3 {
+    fn from((x, y, z): (f64, f64, f64)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
 impl fmt::Display for DVec3 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", (self.x, self.y, self.z))
+        let components = [self.x, self.y, self.z];
+        scalar::fmt_components(f, &components, 3, fmt::Debug::fmt)
     }
 }
 
@@ -286,13 +508,24 @@ pub struct DVec4 {
 
 impl DVec4 {
     /// Full constructor.
-    pub fn new(x: f64, y: f64, z: f64, w: f64) -> Self {
+    pub const fn new(x: f64, y: f64, z: f64, w: f64) -> Self {
         DVec4 { x, y, z, w }
     }
 
     /// Zero constructor.
-    pub fn zero() -> Self {
-        Default::default()
+    pub const fn zero() -> Self {
+        Self::new(0.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Homogeneous point constructor, with `w = 1` so that translation applies.
+    pub fn point(xyz: DVec3) -> Self {
+        Self::new(xyz.x, xyz.y, xyz.z, 1.0)
+    }
+
+    /// Homogeneous direction constructor, with `w = 0` so that translation
+    /// does not apply.
+    pub fn direction(xyz: DVec3) -> Self {
+        Self::new(xyz.x, xyz.y, xyz.z, 0.0)
     }
 
     /// Returns the XY components of the vector.
@@ -304,6 +537,60 @@ impl DVec4 {
     pub fn xyz(self) -> DVec3 {
         DVec3::new(self.x, self.y, self.z)
     }
+
+    /// Returns the XYZ components of the vector, discarding W.
+    ///
+    /// Equivalent to `xyz`.
+    pub fn truncate(self) -> DVec3 {
+        self.xyz()
+    }
+
+    /// Returns the vector whose `i`-th component is the component of `self`
+    /// at index `indices[i]`, e.g. `[2, 0, 1]` gives `(z, x, y)`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if any index is out of range.
+    pub fn shuffle(self, indices: [usize; 4]) -> Self {
+        let a = self.to_array();
+        indices.map(|i| a[i]).into()
+    }
+
+    /// Returns the cross product of the XYZ components of two vectors, with a
+    /// zero W component.
+    pub fn cross(self, rhs: Self) -> Self {
+        let v = self.xyz().cross(rhs.xyz());
+        Self::new(v.x, v.y, v.z, 0.0)
+    }
+
+    /// Interprets the vector as an sRGB-encoded RGBA color and returns the
+    /// equivalent color in linear RGB.
+    ///
+    /// The piecewise sRGB transfer function is applied to the XYZ (RGB)
+    /// components, which are expected to be in the range `[0, 1]`. The W
+    /// (alpha) component is unchanged.
+    pub fn to_linear(self) -> Self {
+        Self::new(
+            scalar::srgb_to_linear(self.x),
+            scalar::srgb_to_linear(self.y),
+            scalar::srgb_to_linear(self.z),
+            self.w,
+        )
+    }
+
+    /// Interprets the vector as a linear RGBA color and returns the equivalent
+    /// sRGB-encoded color.
+    ///
+    /// This is the inverse of `to_linear`. The W (alpha) component is
+    /// unchanged.
+    pub fn to_srgb(self) -> Self {
+        Self::new(
+            scalar::linear_to_srgb(self.x),
+            scalar::linear_to_srgb(self.y),
+            scalar::linear_to_srgb(self.z),
+            self.w,
+        )
+    }
 }
 
 impl From<f32> for DVec4 {
@@ -338,9 +625,16 @@ impl<T: Into<DVec3>> From<(T, f64)> for DVec4 {
     }
 }
 
+impl From<(f64, f64, f64, f64)> for DVec4 {
+    fn from((x, y, z, w): (f64, f64, f64, f64)) -> Self {
+        Self::new(x, y, z, w)
+    }
+}
+
 impl fmt::Display for DVec4 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", (self.x, self.y, self.z, self.w))
+        let components = [self.x, self.y, self.z, self.w];
+        scalar::fmt_components(f, &components, 4, fmt::Debug::fmt)
     }
 }
 
@@ -352,6 +646,139 @@ impl DVec3 {
         let v: [f64; 3] = a.cross(*b).into();
         v.into()
     }
+
+    /// Returns the cross product of two vectors, taking both by reference.
+    ///
+    /// Equivalent to `self.cross(*rhs)`.
+    pub fn cross_ref(&self, rhs: &Self) -> Self {
+        self.cross(*rhs)
+    }
+
+    /// Returns the unit normal of the triangle `abc`.
+    ///
+    /// The normal faces the side from which the vertices appear in
+    /// counter-clockwise order.
+    pub fn triangle_normal(a: Self, b: Self, c: Self) -> Self {
+        (b - a).cross(c - a).normalize()
+    }
+
+    /// Returns the area of the triangle `abc`.
+    pub fn triangle_area(a: Self, b: Self, c: Self) -> f64 {
+        0.5 * (b - a).cross(c - a).length()
+    }
+
+    /// Returns `true` if the two vectors are perpendicular.
+    ///
+    /// `epsilon` is the tolerance on the cosine of the angle between the
+    /// vectors.
+    pub fn is_perpendicular(self, other: Self, epsilon: f64) -> bool {
+        self.dot(other).abs() <= epsilon * self.length() * other.length()
+    }
+
+    /// Returns `true` if the two vectors are parallel or antiparallel.
+    ///
+    /// `epsilon` is the tolerance on the sine of the angle between the vectors.
+    pub fn is_parallel(self, other: Self, epsilon: f64) -> bool {
+        self.cross(other).length() <= epsilon * self.length() * other.length()
+    }
+
+    /// Returns the `index`-th of `count` points distributed roughly evenly
+    /// over the unit sphere, using the spherical Fibonacci lattice.
+    ///
+    /// A single point is placed at the pole `(0, 1, 0)`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `index` is not less than `count`, hence always if `count` is
+    /// zero.
+    pub fn fibonacci_sphere(index: usize, count: usize) -> Self {
+        assert!(index < count, "index out of range");
+        if count == 1 {
+            return dvec3!(0.0, 1.0, 0.0);
+        }
+        let golden_angle = std::f64::consts::PI * (3.0 - f64::sqrt(5.0));
+        let y = 1.0 - 2.0 * (index as f64 + 0.5) / count as f64;
+        let radius = (1.0 - y * y).sqrt();
+        let phi = golden_angle * index as f64;
+        dvec3!(phi.cos() * radius, y, phi.sin() * radius)
+    }
+
+    /// Returns the signed distance from the point to the surface of an
+    /// axis-aligned box centered at the origin.
+    ///
+    /// The distance is negative inside the box.
+    pub fn sdf_box(self, half_extents: Self) -> f64 {
+        let q = dvec3!(self.x.abs(), self.y.abs(), self.z.abs()) - half_extents;
+        let outside = dvec3!(q.x.max(0.0), q.y.max(0.0), q.z.max(0.0)).length();
+        let inside = q.x.max(q.y).max(q.z).min(0.0);
+        outside + inside
+    }
+
+    /// Returns two unit vectors that together with `self` form a right-handed
+    /// orthonormal basis, such that `a.cross(b) == self`.
+    ///
+    /// `self` must be normalized. Uses the branchless construction of Duff et
+    /// al., "Building an Orthonormal Basis, Revisited" (2017), which is robust
+    /// for all directions including the poles.
+    pub fn any_orthonormal_pair(self) -> (Self, Self) {
+        let sign = 1.0f64.copysign(self.z);
+        let a = -1.0 / (sign + self.z);
+        let b = self.x * self.y * a;
+        (
+            Self::new(1.0 + sign * self.x * self.x * a, sign * b, -sign * self.x),
+            Self::new(b, sign + self.y * self.y * a, -self.y),
+        )
+    }
+
+    /// Returns the spherical linear interpolation between two unit directions,
+    /// rotating at constant angular velocity from `self` to `other`.
+    ///
+    /// Both vectors should be normalized. Nearly parallel directions are
+    /// linearly interpolated and renormalized instead. The result is undefined
+    /// for opposite directions.
+    pub fn slerp(self, other: Self, t: f64) -> Self {
+        scalar::slerp_direction(self.into(), other.into(), t).into()
+    }
+
+    /// Returns the normal `self` oriented to face against `incident`, i.e.
+    /// `self` if `reference.dot(incident) < 0` and `-self` otherwise.
+    ///
+    /// Equivalent to the GLSL `faceforward` function.
+    pub fn face_forward(self, incident: Self, reference: Self) -> Self {
+        if reference.dot(incident) < 0.0 {
+            self
+        } else {
+            -self
+        }
+    }
+
+    /// Returns the spherical coordinates `(radius, azimuth, inclination)` of
+    /// the vector, with Y as the polar axis.
+    ///
+    /// The inclination is the angle from the positive Y axis, in the range
+    /// `[0, π]`, and the azimuth is the angle in the XZ plane from the positive
+    /// X axis towards the positive Z axis, in the range `[-π, π]`. Both angles
+    /// are zero for the zero vector, and the azimuth is zero on the Y axis.
+    pub fn to_spherical(self) -> (f64, f64, f64) {
+        let radius = self.length();
+        if radius == 0.0 {
+            return (0.0, 0.0, 0.0);
+        }
+        let azimuth = self.z.atan2(self.x);
+        let inclination = self.x.hypot(self.z).atan2(self.y);
+        (radius, azimuth, inclination)
+    }
+
+    /// Constructor from spherical coordinates, as per `to_spherical`.
+    pub fn from_spherical(radius: f64, azimuth: f64, inclination: f64) -> Self {
+        let (sin_inclination, cos_inclination) = inclination.sin_cos();
+        let (sin_azimuth, cos_azimuth) = azimuth.sin_cos();
+        dvec3!(
+            radius * sin_inclination * cos_azimuth,
+            radius * cos_inclination,
+            radius * sin_inclination * sin_azimuth
+        )
+    }
 }
 
 impl Vec3 {
@@ -362,6 +789,139 @@ impl Vec3 {
         let v: [f32; 3] = a.cross(*b).into();
         v.into()
     }
+
+    /// Returns the cross product of two vectors, taking both by reference.
+    ///
+    /// Equivalent to `self.cross(*rhs)`.
+    pub fn cross_ref(&self, rhs: &Self) -> Self {
+        self.cross(*rhs)
+    }
+
+    /// Returns the unit normal of the triangle `abc`.
+    ///
+    /// The normal faces the side from which the vertices appear in
+    /// counter-clockwise order.
+    pub fn triangle_normal(a: Self, b: Self, c: Self) -> Self {
+        (b - a).cross(c - a).normalize()
+    }
+
+    /// Returns the area of the triangle `abc`.
+    pub fn triangle_area(a: Self, b: Self, c: Self) -> f32 {
+        0.5 * (b - a).cross(c - a).length()
+    }
+
+    /// Returns `true` if the two vectors are perpendicular.
+    ///
+    /// `epsilon` is the tolerance on the cosine of the angle between the
+    /// vectors.
+    pub fn is_perpendicular(self, other: Self, epsilon: f32) -> bool {
+        self.dot(other).abs() <= epsilon * self.length() * other.length()
+    }
+
+    /// Returns `true` if the two vectors are parallel or antiparallel.
+    ///
+    /// `epsilon` is the tolerance on the sine of the angle between the vectors.
+    pub fn is_parallel(self, other: Self, epsilon: f32) -> bool {
+        self.cross(other).length() <= epsilon * self.length() * other.length()
+    }
+
+    /// Returns the `index`-th of `count` points distributed roughly evenly
+    /// over the unit sphere, using the spherical Fibonacci lattice.
+    ///
+    /// A single point is placed at the pole `(0, 1, 0)`.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `index` is not less than `count`, hence always if `count` is
+    /// zero.
+    pub fn fibonacci_sphere(index: usize, count: usize) -> Self {
+        assert!(index < count, "index out of range");
+        if count == 1 {
+            return vec3!(0.0, 1.0, 0.0);
+        }
+        let golden_angle = std::f32::consts::PI * (3.0 - f32::sqrt(5.0));
+        let y = 1.0 - 2.0 * (index as f32 + 0.5) / count as f32;
+        let radius = (1.0 - y * y).sqrt();
+        let phi = golden_angle * index as f32;
+        vec3!(phi.cos() * radius, y, phi.sin() * radius)
+    }
+
+    /// Returns the signed distance from the point to the surface of an
+    /// axis-aligned box centered at the origin.
+    ///
+    /// The distance is negative inside the box.
+    pub fn sdf_box(self, half_extents: Self) -> f32 {
+        let q = vec3!(self.x.abs(), self.y.abs(), self.z.abs()) - half_extents;
+        let outside = vec3!(q.x.max(0.0), q.y.max(0.0), q.z.max(0.0)).length();
+        let inside = q.x.max(q.y).max(q.z).min(0.0);
+        outside + inside
+    }
+
+    /// Returns two unit vectors that together with `self` form a right-handed
+    /// orthonormal basis, such that `a.cross(b) == self`.
+    ///
+    /// `self` must be normalized. Uses the branchless construction of Duff et
+    /// al., "Building an Orthonormal Basis, Revisited" (2017), which is robust
+    /// for all directions including the poles.
+    pub fn any_orthonormal_pair(self) -> (Self, Self) {
+        let sign = 1.0f32.copysign(self.z);
+        let a = -1.0 / (sign + self.z);
+        let b = self.x * self.y * a;
+        (
+            Self::new(1.0 + sign * self.x * self.x * a, sign * b, -sign * self.x),
+            Self::new(b, sign + self.y * self.y * a, -self.y),
+        )
+    }
+
+    /// Returns the spherical linear interpolation between two unit directions,
+    /// rotating at constant angular velocity from `self` to `other`.
+    ///
+    /// Both vectors should be normalized. Nearly parallel directions are
+    /// linearly interpolated and renormalized instead. The result is undefined
+    /// for opposite directions.
+    pub fn slerp(self, other: Self, t: f32) -> Self {
+        scalar::slerp_direction(self.into(), other.into(), t).into()
+    }
+
+    /// Returns the normal `self` oriented to face against `incident`, i.e.
+    /// `self` if `reference.dot(incident) < 0` and `-self` otherwise.
+    ///
+    /// Equivalent to the GLSL `faceforward` function.
+    pub fn face_forward(self, incident: Self, reference: Self) -> Self {
+        if reference.dot(incident) < 0.0 {
+            self
+        } else {
+            -self
+        }
+    }
+
+    /// Returns the spherical coordinates `(radius, azimuth, inclination)` of
+    /// the vector, with Y as the polar axis.
+    ///
+    /// The inclination is the angle from the positive Y axis, in the range
+    /// `[0, π]`, and the azimuth is the angle in the XZ plane from the positive
+    /// X axis towards the positive Z axis, in the range `[-π, π]`. Both angles
+    /// are zero for the zero vector, and the azimuth is zero on the Y axis.
+    pub fn to_spherical(self) -> (f32, f32, f32) {
+        let radius = self.length();
+        if radius == 0.0 {
+            return (0.0, 0.0, 0.0);
+        }
+        let azimuth = self.z.atan2(self.x);
+        let inclination = self.x.hypot(self.z).atan2(self.y);
+        (radius, azimuth, inclination)
+    }
+
+    /// Constructor from spherical coordinates, as per `to_spherical`.
+    pub fn from_spherical(radius: f32, azimuth: f32, inclination: f32) -> Self {
+        let (sin_inclination, cos_inclination) = inclination.sin_cos();
+        let (sin_azimuth, cos_azimuth) = azimuth.sin_cos();
+        vec3!(
+            radius * sin_inclination * cos_azimuth,
+            radius * cos_inclination,
+            radius * sin_inclination * sin_azimuth
+        )
+    }
 }
 
 macro_rules! impl_angle {
@@ -380,8 +940,24 @@ macro_rules! impl_angle {
 }
 
 macro_rules! impl_vector {
-    ($self:ty, $base:ty, $inner:ty, $array:ty) => {
+    ($self:ty, $base:ty, $inner:ty, $array:ty, $($field:ident),*) => {
         impl $self {
+            /// Returns the components as an array.
+            ///
+            /// Unlike the `Into` conversion, this copies the fields one by one
+            /// and does not depend on the memory layout of the vector.
+            pub fn to_array(self) -> $array {
+                [$(self.$field),*]
+            }
+
+            /// Alias of `to_array` for callers holding a reference.
+            ///
+            /// Returns a copy rather than a borrowed view; use the `AsRef`
+            /// conversion to borrow the components in place.
+            pub fn array_ref(&self) -> $array {
+                self.to_array()
+            }
+
             /// Returns the dot product of two vectors.
             pub fn dot(self, rhs: $self) -> $base {
                 use cgmath::InnerSpace;
@@ -415,6 +991,107 @@ macro_rules! impl_vector {
                 let v: $array = a.normalize().into();
                 v.into()
             }
+
+            /// Returns the component-wise Euclidean remainder of `self` divided by
+            /// `rhs`, such that each component lies in `[0, rhs)` for positive
+            /// `rhs` components.
+            pub fn rem_euclid(self, rhs: $self) -> $self {
+                scalar::zip_map(self.into(), rhs.into(), <$base>::rem_euclid).into()
+            }
+
+            /// Returns the component-wise Hermite interpolation between `edge0`
+            /// and `edge1`.
+            ///
+            /// Each component is 0 if less than or equal to `edge0`, 1 if greater
+            /// than or equal to `edge1`, and smoothly interpolated in between.
+            pub fn smoothstep(self, edge0: $base, edge1: $base) -> $self {
+                scalar::map(self.into(), |x| scalar::smoothstep(edge0, edge1, x)).into()
+            }
+
+            /// Returns the component-wise quintic Hermite interpolation between
+            /// `edge0` and `edge1`.
+            ///
+            /// Like `smoothstep` but with zero first and second derivatives at
+            /// the edges.
+            pub fn smootherstep(self, edge0: $base, edge1: $base) -> $self {
+                scalar::map(self.into(), |x| scalar::smootherstep(edge0, edge1, x)).into()
+            }
+
+            /// Raises each component to the power `n`.
+            pub fn powf(self, n: $base) -> $self {
+                scalar::map(self.into(), |x| x.powf(n)).into()
+            }
+
+            /// Returns the component-wise square root.
+            pub fn sqrt(self) -> $self {
+                scalar::map(self.into(), <$base>::sqrt).into()
+            }
+
+            /// Returns the component-wise reciprocal, `1 / x`.
+            pub fn recip(self) -> $self {
+                scalar::map(self.into(), <$base>::recip).into()
+            }
+
+            /// Returns the component-wise sign, 1 for positive values and positive
+            /// zero, -1 for negative values and negative zero, and NaN for NaN.
+            pub fn signum(self) -> $self {
+                scalar::map(self.into(), <$base>::signum).into()
+            }
+
+            /// Clamps each component to the range `[0, 1]`.
+            pub fn saturate(self) -> $self {
+                scalar::map(self.into(), |x| x.clamp(0.0, 1.0)).into()
+            }
+
+            /// Rounds each component to the nearest multiple of the corresponding
+            /// component of `grid`.
+            ///
+            /// Components whose grid spacing is zero are left unchanged.
+            pub fn snap(self, grid: $self) -> $self {
+                scalar::zip_map(self.into(), grid.into(), |x, g| {
+                    if g == 0.0 {
+                        x
+                    } else {
+                        (x / g).round() * g
+                    }
+                })
+                .into()
+            }
+
+            /// Returns the component-wise absolute difference, `|self - other|`.
+            pub fn abs_diff(self, other: $self) -> $self {
+                scalar::zip_map(self.into(), other.into(), |a, b| (a - b).abs()).into()
+            }
+
+            /// Returns the largest absolute difference between corresponding
+            /// components, or NaN if any difference is NaN.
+            pub fn max_abs_diff(self, other: $self) -> $base {
+                let d: $array = self.abs_diff(other).into();
+                d.iter()
+                    .fold(0.0, |max, &x| if x > max || x.is_nan() { x } else { max })
+            }
+
+            /// Clamps each component to the range `[lo, hi]`.
+            ///
+            /// Components that are NaN are replaced with the corresponding
+            /// component of `lo`.
+            pub fn clamp_nan_safe(self, lo: $self, hi: $self) -> $self {
+                let mut v: $array = self.into();
+                let lo: $array = lo.into();
+                let hi: $array = hi.into();
+                for ((x, lo), hi) in v.iter_mut().zip(lo.iter()).zip(hi.iter()) {
+                    *x = if x.is_nan() { *lo } else { x.max(*lo).min(*hi) };
+                }
+                v.into()
+            }
+
+            /// Compares two vectors lexicographically, component by component,
+            /// using the total ordering of the underlying float type.
+            ///
+            /// Useful as a deterministic key for sorting and deduplication.
+            pub fn total_cmp(&self, other: &$self) -> cmp::Ordering {
+                cmp::Ordering::Equal$(.then_with(|| self.$field.total_cmp(&other.$field)))*
+            }
         }
 
         impl ops::Add<$self> for $self {
@@ -427,6 +1104,13 @@ macro_rules! impl_vector {
             }
         }
 
+        impl<'a, 'b> ops::Add<&'b $self> for &'a $self {
+            type Output = $self;
+            fn add(self, rhs: &'b $self) -> Self::Output {
+                *self + *rhs
+            }
+        }
+
         impl ops::AddAssign<$self> for $self {
             fn add_assign(&mut self, rhs: $self) {
                 *self = *self + rhs;
@@ -461,12 +1145,28 @@ macro_rules! impl_vector {
             }
         }
 
+        impl<'a, 'b> ops::Sub<&'b $self> for &'a $self {
+            type Output = $self;
+            fn sub(self, rhs: &'b $self) -> Self::Output {
+                *self - *rhs
+            }
+        }
+
         impl ops::SubAssign<$self> for $self {
             fn sub_assign(&mut self, rhs: $self) {
                 *self = *self - rhs;
             }
         }
 
+        impl ops::Neg for $self {
+            type Output = $self;
+            fn neg(self) -> Self::Output {
+                let a: &$inner = self.as_ref().into();
+                let v: $array = (-*a).into();
+                v.into()
+            }
+        }
+
         impl ops::Mul<$self> for $base {
             type Output = $self;
             fn mul(self, arg: $self) -> Self::Output {
@@ -485,12 +1185,34 @@ macro_rules! impl_vector {
             }
         }
 
+        impl<'a> ops::Mul<$base> for &'a $self {
+            type Output = $self;
+            fn mul(self, arg: $base) -> Self::Output {
+                *self * arg
+            }
+        }
+
         impl ops::MulAssign<$base> for $self {
             fn mul_assign(&mut self, rhs: $base) {
                 *self = *self * rhs;
             }
         }
 
+        /// Component-wise (Hadamard) product.
+        impl ops::Mul<$self> for $self {
+            type Output = $self;
+            fn mul(self, rhs: $self) -> Self::Output {
+                scalar::zip_map(self.into(), rhs.into(), |a, b| a * b).into()
+            }
+        }
+
+        /// Component-wise (Hadamard) product.
+        impl ops::MulAssign<$self> for $self {
+            fn mul_assign(&mut self, rhs: $self) {
+                *self = *self * rhs;
+            }
+        }
+
         impl ops::Div<$base> for $self {
             type Output = $self;
             fn div(self, arg: $base) -> Self::Output {
@@ -500,27 +1222,48 @@ macro_rules! impl_vector {
             }
         }
 
+        impl<'a> ops::Div<$base> for &'a $self {
+            type Output = $self;
+            fn div(self, arg: $base) -> Self::Output {
+                *self / arg
+            }
+        }
+
         impl ops::DivAssign<$base> for $self {
             fn div_assign(&mut self, rhs: $base) {
                 *self = *self / rhs;
             }
         }
 
+        // The vector is `#[repr(C)]` with its components in array order, hence
+        // it can be viewed as an array provided the size and alignment agree.
+        const _: () = assert!(
+            mem::size_of::<$self>() == mem::size_of::<$array>()
+                && mem::align_of::<$self>() == mem::align_of::<$array>()
+        );
+
         impl AsRef<$array> for $self {
             fn as_ref(&self) -> &$array {
-                unsafe { mem::transmute(self) }
+                unsafe { &*(self as *const $self as *const $array) }
+            }
+        }
+
+        impl AsMut<$array> for $self {
+            fn as_mut(&mut self) -> &mut $array {
+                unsafe { &mut *(self as *mut $self as *mut $array) }
             }
         }
 
         impl From<$array> for $self {
             fn from(array: $array) -> Self {
-                unsafe { mem::transmute(array) }
+                let [$($field),*] = array;
+                Self { $($field),* }
             }
         }
 
         impl Into<$array> for $self {
             fn into(self) -> $array {
-                unsafe { mem::transmute(self) }
+                self.to_array()
             }
         }
 
@@ -556,16 +1299,32 @@ macro_rules! impl_vector {
                 a.ulps_eq(b, epsilon, max_ulps)
             }
         }
+
+        impl AbsDiffEq for $self {
+            type Epsilon = $base;
+
+            fn default_epsilon() -> Self::Epsilon {
+                <$base>::EPSILON
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                let a: &$array = self.as_ref();
+                let b: &$array = other.as_ref();
+                a.iter()
+                    .zip(b.iter())
+                    .all(|(x, y)| x.abs_diff_eq(y, epsilon))
+            }
+        }
     };
 }
 
-impl_vector!(Vec2, f32, cgmath::Vector2<f32>, [f32; 2]);
-impl_vector!(Vec3, f32, cgmath::Vector3<f32>, [f32; 3]);
-impl_vector!(Vec4, f32, cgmath::Vector4<f32>, [f32; 4]);
+impl_vector!(Vec2, f32, cgmath::Vector2<f32>, [f32; 2], x, y);
+impl_vector!(Vec3, f32, cgmath::Vector3<f32>, [f32; 3], x, y, z);
+impl_vector!(Vec4, f32, cgmath::Vector4<f32>, [f32; 4], x, y, z, w);
 
-impl_vector!(DVec2, f64, cgmath::Vector2<f64>, [f64; 2]);
-impl_vector!(DVec3, f64, cgmath::Vector3<f64>, [f64; 3]);
-impl_vector!(DVec4, f64, cgmath::Vector4<f64>, [f64; 4]);
+impl_vector!(DVec2, f64, cgmath::Vector2<f64>, [f64; 2], x, y);
+impl_vector!(DVec3, f64, cgmath::Vector3<f64>, [f64; 3], x, y, z);
+impl_vector!(DVec4, f64, cgmath::Vector4<f64>, [f64; 4], x, y, z, w);
 
 impl_angle!(Vec2, f32);
 impl_angle!(Vec3, f32);
@@ -586,6 +1345,664 @@ mod tests {
         let sum = vs.iter().sum();
         approx::assert_relative_eq!(sum, vec4!(-2.0, 0.0, 2.0));
     }
+
+    #[test]
+    pub fn vec3_triangle() {
+        use super::{DVec3, Vec3};
+        let (a, b, c) = (
+            vec3!(0.0, 0.0, 0.0),
+            vec3!(1.0, 0.0, 0.0),
+            vec3!(0.0, 1.0, 0.0),
+        );
+        approx::assert_relative_eq!(Vec3::triangle_normal(a, b, c), vec3!(0.0, 0.0, 1.0));
+        approx::assert_relative_eq!(Vec3::triangle_normal(a, c, b), vec3!(0.0, 0.0, -1.0));
+        approx::assert_relative_eq!(Vec3::triangle_area(a, b, c), 0.5);
+
+        let (a, b, c) = (dvec3!(a), dvec3!(b), dvec3!(c));
+        approx::assert_relative_eq!(DVec3::triangle_normal(a, b, c), dvec3!(0.0, 0.0, 1.0));
+        approx::assert_relative_eq!(DVec3::triangle_area(a, b, c), 0.5);
+    }
+
+    #[test]
+    pub fn vec3_sdf_box() {
+        let half_extents = vec3!(1.0, 2.0, 3.0);
+        approx::assert_relative_eq!(vec3!().sdf_box(half_extents), -1.0);
+        approx::assert_relative_eq!(vec3!(0.0, 1.5, 0.0).sdf_box(half_extents), -0.5);
+        approx::assert_relative_eq!(vec3!(3.0, 0.0, 0.0).sdf_box(half_extents), 2.0);
+        approx::assert_relative_eq!(vec3!(2.0, -3.0, 0.0).sdf_box(half_extents), 2.0f32.sqrt());
+        approx::assert_relative_eq!(dvec3!(0.0, 0.0, -5.0).sdf_box(dvec3!(half_extents)), 2.0);
+    }
+
+    #[test]
+    pub fn vec3_perpendicular_parallel() {
+        let (x, y) = (vec3!(1.0, 0.0, 0.0), vec3!(0.0, 2.0, 0.0));
+        assert!(x.is_perpendicular(y, 1.0e-6));
+        assert!(!x.is_parallel(y, 1.0e-6));
+        assert!(x.is_parallel(vec3!(3.0, 0.0, 0.0), 1.0e-6));
+        assert!(x.is_parallel(vec3!(-0.5, 0.0, 0.0), 1.0e-6));
+        assert!(!x.is_perpendicular(vec3!(-0.5, 0.0, 0.0), 1.0e-6));
+        assert!(!x.is_perpendicular(vec3!(1.0, 1.0, 0.0), 1.0e-6));
+        assert!(!x.is_parallel(vec3!(1.0, 1.0, 0.0), 1.0e-6));
+
+        let (x, z) = (dvec3!(1.0, 1.0, 0.0), dvec3!(0.0, 0.0, 1.0));
+        assert!(x.is_perpendicular(z, 1.0e-12));
+        assert!(x.is_parallel(dvec3!(-2.0, -2.0, 0.0), 1.0e-12));
+    }
+
+    #[test]
+    pub fn vec3_fibonacci_sphere() {
+        use super::{DVec3, Vec3};
+
+        fn max_gap(count: usize) -> f32 {
+            let points: Vec<_> = (0..count)
+                .map(|i| Vec3::fibonacci_sphere(i, count))
+                .collect();
+            points
+                .iter()
+                .enumerate()
+                .map(|(i, p)| {
+                    points
+                        .iter()
+                        .enumerate()
+                        .filter(|&(j, _)| i != j)
+                        .map(|(_, q)| (*p - *q).length())
+                        .fold(f32::INFINITY, f32::min)
+                })
+                .fold(0.0, f32::max)
+        }
+
+        for i in 0..100 {
+            approx::assert_relative_eq!(
+                Vec3::fibonacci_sphere(i, 100).length(),
+                1.0,
+                epsilon = 1.0e-6
+            );
+            approx::assert_relative_eq!(
+                DVec3::fibonacci_sphere(i, 100).length(),
+                1.0,
+                epsilon = 1.0e-12
+            );
+        }
+        assert!(max_gap(200) < max_gap(50));
+        assert_eq!(Vec3::fibonacci_sphere(0, 1), Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!(DVec3::fibonacci_sphere(0, 1), DVec3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn vec3_fibonacci_sphere_empty() {
+        super::Vec3::fibonacci_sphere(0, 0);
+    }
+
+    #[test]
+    pub fn vec_rem_euclid() {
+        assert_eq!(vec2!(-0.25, 1.5).rem_euclid(vec2!(1, 1)), vec2!(0.75, 0.5));
+        assert_eq!(
+            dvec3!(-3.0, 7.0, 2.0).rem_euclid(dvec3!(2.0, 4.0, 2.0)),
+            dvec3!(1.0, 3.0, 0.0)
+        );
+    }
+
+    #[test]
+    pub fn vec_smoothstep() {
+        assert_eq!(
+            vec4!(-1.0, 1.0, 2.0, 3.0).smoothstep(1.0, 3.0),
+            vec4!(0.0, 0.0, 0.5, 1.0)
+        );
+        assert_eq!(dvec2!(0.0, 10.0).smoothstep(2.0, 4.0), dvec2!(0.0, 1.0));
+        approx::assert_relative_eq!(vec2!(0.25).smoothstep(0.0, 1.0), vec2!(0.15625));
+
+        assert_eq!(
+            vec4!(-1.0, 1.0, 2.0, 3.0).smootherstep(1.0, 3.0),
+            vec4!(0.0, 0.0, 0.5, 1.0)
+        );
+        approx::assert_relative_eq!(vec2!(0.25).smootherstep(0.0, 1.0), vec2!(0.103515625));
+    }
+
+    #[test]
+    pub fn vec_clamp_nan_safe() {
+        let lo = vec3!(-1.0);
+        let hi = vec3!(1.0);
+        assert_eq!(
+            vec3!(-2.0, 0.5, 2.0).clamp_nan_safe(lo, hi),
+            vec3!(-1.0, 0.5, 1.0)
+        );
+        assert_eq!(
+            vec3!(0.5, f32::NAN, 0.5).clamp_nan_safe(lo, hi),
+            vec3!(0.5, -1.0, 0.5)
+        );
+        assert_eq!(
+            dvec2!(f64::NAN, 3.0).clamp_nan_safe(dvec2!(0.0), dvec2!(2.0)),
+            dvec2!(0.0, 2.0)
+        );
+    }
+
+    #[test]
+    pub fn vec_abs_diff_eq() {
+        use super::{DVec2, Vec4};
+        approx05::assert_abs_diff_eq!(
+            vec4!(1.0, 2.0, 3.0, 4.0),
+            Vec4::new(1.0, 2.0, 3.0, 4.0 + 1.0e-7),
+            epsilon = 1.0e-6
+        );
+        approx05::assert_abs_diff_ne!(
+            vec4!(1.0, 2.0, 3.0, 4.0),
+            Vec4::new(1.0, 2.0, 3.0, 4.1),
+            epsilon = 1.0e-6
+        );
+        approx05::assert_abs_diff_eq!(
+            DVec2::new(1.0, 2.0),
+            DVec2::new(1.0, 2.0 - 1.0e-12),
+            epsilon = 1.0e-9
+        );
+    }
+
+    #[test]
+    pub fn vec_to_array() {
+        use super::{DVec2, Vec3, Vec4};
+        let v = Vec4::new(1.0, -2.0, 3.5, 0.25);
+        let a: [f32; 4] = v.into();
+        assert_eq!(v.to_array(), a);
+        assert_eq!(&v.array_ref(), v.as_ref());
+        let v = Vec3::new(-0.0, f32::MAX, f32::MIN_POSITIVE);
+        let a: [f32; 3] = v.into();
+        assert_eq!(v.to_array(), a);
+        let v = DVec2::new(1.0e300, -7.0);
+        let a: [f64; 2] = v.into();
+        assert_eq!(v.to_array(), a);
+        assert_eq!(&v.array_ref(), v.as_ref());
+    }
+
+    #[test]
+    pub fn vec_array_view_field_order() {
+        use super::{DVec2, DVec3, DVec4, Vec2, Vec3, Vec4};
+        assert_eq!(Vec2::new(1.0, 2.0).as_ref(), &[1.0, 2.0]);
+        assert_eq!(Vec3::new(1.0, 2.0, 3.0).as_ref(), &[1.0, 2.0, 3.0]);
+        assert_eq!(
+            Vec4::new(1.0, 2.0, 3.0, 4.0).as_ref(),
+            &[1.0, 2.0, 3.0, 4.0]
+        );
+        assert_eq!(DVec2::new(1.0, 2.0).as_ref(), &[1.0, 2.0]);
+        assert_eq!(DVec3::new(1.0, 2.0, 3.0).as_ref(), &[1.0, 2.0, 3.0]);
+        assert_eq!(
+            DVec4::new(1.0, 2.0, 3.0, 4.0).as_ref(),
+            &[1.0, 2.0, 3.0, 4.0]
+        );
+        assert_eq!(Vec3::from([1.0, 2.0, 3.0]), Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(
+            DVec4::from([1.0, 2.0, 3.0, 4.0]),
+            DVec4::new(1.0, 2.0, 3.0, 4.0)
+        );
+    }
+
+    #[test]
+    pub fn vec4_cross() {
+        use super::{DVec4, Vec3, Vec4};
+        let a = Vec4::new(1.0, -2.0, 0.5, 1.0);
+        let b = Vec4::new(3.0, 4.0, -1.0, 7.0);
+        let c = a.cross(b);
+        assert_eq!(c.xyz(), a.xyz().cross(b.xyz()));
+        assert_eq!(c.w, 0.0);
+        assert_eq!(
+            Vec4::new(1.0, 0.0, 0.0, 0.0).cross(Vec4::new(0.0, 1.0, 0.0, 0.0)),
+            Vec4::new(0.0, 0.0, 1.0, 0.0)
+        );
+        assert_eq!(c.xyz(), Vec3::new(0.0, 2.5, 10.0));
+
+        let a = DVec4::new(0.0, 2.0, 0.0, 1.0);
+        let b = DVec4::new(0.0, 0.0, 3.0, 1.0);
+        assert_eq!(a.cross(b), DVec4::new(6.0, 0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    pub fn vec3_any_orthonormal_pair() {
+        use super::{DVec3, Vec3};
+        let directions = [
+            Vec3::new(0.0, 0.0, 1.0),
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec3::new(1.0e-4, 0.0, -1.0).normalize(),
+            Vec3::new(0.0, -1.0e-7, 1.0).normalize(),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(1.0, 2.0, 3.0).normalize(),
+            Vec3::new(-3.0, 0.5, -0.2).normalize(),
+        ];
+        for &n in directions.iter() {
+            let (a, b) = n.any_orthonormal_pair();
+            approx::assert_relative_eq!(a.length(), 1.0, epsilon = 1.0e-6);
+            approx::assert_relative_eq!(b.length(), 1.0, epsilon = 1.0e-6);
+            approx::assert_relative_eq!(a.dot(b), 0.0, epsilon = 1.0e-6);
+            approx::assert_relative_eq!(a.dot(n), 0.0, epsilon = 1.0e-6);
+            approx::assert_relative_eq!(b.dot(n), 0.0, epsilon = 1.0e-6);
+            approx::assert_relative_eq!(a.cross(b), n, epsilon = 1.0e-6);
+        }
+
+        let n = DVec3::new(0.3, -0.4, -1.0e-9).normalize();
+        let (a, b) = n.any_orthonormal_pair();
+        approx::assert_relative_eq!(a.dot(b), 0.0, epsilon = 1.0e-12);
+        approx::assert_relative_eq!(a.cross(b), n, epsilon = 1.0e-12);
+    }
+
+    #[test]
+    pub fn vec4_srgb() {
+        use super::{DVec4, Vec4};
+        let black = Vec4::new(0.0, 0.0, 0.0, 0.25);
+        assert_eq!(black.to_linear(), black);
+        assert_eq!(black.to_srgb(), black);
+        let white = Vec4::new(1.0, 1.0, 1.0, 1.0);
+        approx::assert_relative_eq!(white.to_linear(), white, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(white.to_srgb(), white, epsilon = 1.0e-6);
+
+        let gray = Vec4::new(0.5, 0.5, 0.5, 0.5);
+        approx::assert_relative_eq!(gray.to_linear().x, 0.214_041, epsilon = 1.0e-6);
+        assert_eq!(gray.to_linear().w, 0.5);
+        approx::assert_relative_eq!(gray.to_linear().to_srgb(), gray, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(gray.to_srgb().to_linear(), gray, epsilon = 1.0e-6);
+
+        let color = DVec4::new(0.01, 0.2, 0.73, 0.9);
+        approx::assert_relative_eq!(color.to_linear().to_srgb(), color, epsilon = 1.0e-12);
+    }
+
+    #[test]
+    pub fn vec_display_precision() {
+        use super::DVec2;
+        assert_eq!(
+            format!("{:.2}", vec3!(1.234, 5.678, 9.0)),
+            "(1.23, 5.68, 9.00)"
+        );
+        assert_eq!(format!("{}", vec3!(1.0, 2.5, -3.0)), "(1.0, 2.5, -3.0)");
+        assert_eq!(format!("{:5.1}", DVec2::new(1.25, -10.0)), "(  1.2, -10.0)");
+        assert_eq!(format!("{:.0}", vec4!(0.4, 1.6, 2.0, 3.0)), "(0, 2, 2, 3)");
+    }
+
+    #[test]
+    pub fn vec_from_tuple() {
+        use super::{DVec2, DVec3, DVec4, Vec2, Vec3, Vec4};
+        assert_eq!(Vec2::from((1.0, 2.0)), Vec2::new(1.0, 2.0));
+        assert_eq!(Vec3::from((1.0, 2.0, 3.0)), Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(
+            Vec4::from((1.0, 2.0, 3.0, 4.0)),
+            Vec4::new(1.0, 2.0, 3.0, 4.0)
+        );
+        assert_eq!(DVec2::from((1.0, 2.0)), DVec2::new(1.0, 2.0));
+        assert_eq!(DVec3::from((1.0, 2.0, 3.0)), DVec3::new(1.0, 2.0, 3.0));
+        assert_eq!(
+            DVec4::from((1.0, 2.0, 3.0, 4.0)),
+            DVec4::new(1.0, 2.0, 3.0, 4.0)
+        );
+        let v: Vec3 = ((1.0, 2.0), 3.0).into();
+        assert_eq!(v, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(vec3!((4.0, 5.0, 6.0)), Vec3::new(4.0, 5.0, 6.0));
+    }
+
+    #[test]
+    pub fn vec3_slerp() {
+        use super::{DVec3, Vec3};
+        let a = Vec3::new(1.0, 0.0, 0.0);
+        let b = Vec3::new(0.0, 0.6, 0.8);
+        approx::assert_relative_eq!(a.slerp(b, 0.0), a, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(a.slerp(b, 1.0), b, epsilon = 1.0e-6);
+        let mid = a.slerp(b, 0.5);
+        approx::assert_relative_eq!(mid.length(), 1.0, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(mid.dot(a), mid.dot(b), epsilon = 1.0e-6);
+        approx::assert_relative_eq!(mid, (a + b).normalize(), epsilon = 1.0e-6);
+        let quarter = a.slerp(b, 0.25);
+        approx::assert_relative_eq!(
+            quarter.dot(a).acos(),
+            0.25 * std::f32::consts::FRAC_PI_2,
+            epsilon = 1.0e-6
+        );
+
+        let a = DVec3::new(0.0, 0.0, 1.0);
+        let b = DVec3::new(1.0e-5, 0.0, 1.0).normalize();
+        let mid = a.slerp(b, 0.5);
+        approx::assert_relative_eq!(mid.length(), 1.0, epsilon = 1.0e-12);
+        approx::assert_relative_eq!(mid.dot(a), mid.dot(b), epsilon = 1.0e-12);
+    }
+
+    #[test]
+    pub fn vec_elementwise_float_ops() {
+        use super::{DVec2, Vec3, Vec4};
+        assert_eq!(Vec3::new(4.0, 9.0, 16.0).sqrt(), Vec3::new(2.0, 3.0, 4.0));
+        assert_eq!(
+            Vec3::new(2.0, -3.0, 0.5).powf(2.0),
+            Vec3::new(4.0, 9.0, 0.25)
+        );
+        assert_eq!(
+            Vec4::new(2.0, -4.0, 0.5, 1.0).recip(),
+            Vec4::new(0.5, -0.25, 2.0, 1.0)
+        );
+        assert_eq!(
+            Vec4::new(-2.0, 3.0, -0.0, 0.0).signum(),
+            Vec4::new(-1.0, 1.0, -1.0, 1.0)
+        );
+        assert_eq!(DVec2::new(-7.5, 1.0e-300).signum(), DVec2::new(-1.0, 1.0));
+        assert_eq!(DVec2::new(27.0, 8.0).powf(1.0 / 3.0).x.round(), 3.0);
+    }
+
+    #[test]
+    pub fn vec_saturate() {
+        use super::{DVec3, Vec2, Vec4};
+        assert_eq!(Vec2::new(-0.5, 1.5).saturate(), Vec2::new(0.0, 1.0));
+        assert_eq!(
+            Vec4::new(0.25, -3.0, 1.0, 0.0).saturate(),
+            Vec4::new(0.25, 0.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            DVec3::new(0.5, 2.0, -1.0e-9).saturate(),
+            DVec3::new(0.5, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    pub fn vec_total_cmp() {
+        use super::{DVec2, Vec3};
+        use std::cmp::Ordering;
+        let a = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(a.total_cmp(&a), Ordering::Equal);
+        assert_eq!(a.total_cmp(&Vec3::new(1.0, 2.0, 4.0)), Ordering::Less);
+        assert_eq!(a.total_cmp(&Vec3::new(0.0, 9.0, 9.0)), Ordering::Greater);
+        assert_eq!(
+            Vec3::new(-0.0, 0.0, 0.0).total_cmp(&Vec3::new(0.0, 0.0, 0.0)),
+            Ordering::Less
+        );
+
+        let nan = DVec2::new(f64::NAN, 0.0);
+        assert_eq!(nan.total_cmp(&nan), Ordering::Equal);
+        assert_eq!(
+            nan.total_cmp(&DVec2::new(f64::INFINITY, 0.0)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            DVec2::new(0.0, -f64::NAN).total_cmp(&DVec2::new(0.0, f64::NEG_INFINITY)),
+            Ordering::Less
+        );
+
+        let mut v = [
+            DVec2::new(1.0, f64::NAN),
+            DVec2::new(1.0, 0.5),
+            DVec2::new(-1.0, 3.0),
+        ];
+        v.sort_by(DVec2::total_cmp);
+        assert_eq!(v[0], DVec2::new(-1.0, 3.0));
+        assert_eq!(v[1], DVec2::new(1.0, 0.5));
+        assert!(v[2].y.is_nan());
+    }
+
+    #[test]
+    pub fn vec_extend_truncate() {
+        use super::{DVec2, DVec4, Vec2, Vec4};
+        assert_eq!(vec3!(1.0, 2.0, 3.0).extend(4.0), vec4!(1.0, 2.0, 3.0, 4.0));
+        assert_eq!(Vec2::new(1.0, 2.0).extend(3.0), vec3!(1.0, 2.0, 3.0));
+        assert_eq!(
+            Vec4::new(1.0, 2.0, 3.0, 4.0).truncate(),
+            vec3!(1.0, 2.0, 3.0)
+        );
+        assert_eq!(vec3!(1.0, 2.0, 3.0).truncate(), Vec2::new(1.0, 2.0));
+        assert_eq!(
+            DVec2::new(5.0, 6.0).extend(7.0).extend(8.0),
+            DVec4::new(5.0, 6.0, 7.0, 8.0)
+        );
+        assert_eq!(
+            DVec4::new(5.0, 6.0, 7.0, 8.0).truncate().truncate(),
+            DVec2::new(5.0, 6.0)
+        );
+    }
+
+    #[test]
+    pub fn vec_hash_bits() {
+        use super::{Vec2, Vec4};
+        use std::collections::HashMap;
+        assert_eq!(
+            Vec2::new(1.0, 2.0).hash_bits(),
+            Vec2::new(1.0, 2.0).hash_bits()
+        );
+        assert_ne!(
+            Vec2::new(1.0, 2.0).hash_bits(),
+            Vec2::new(2.0, 1.0).hash_bits()
+        );
+        assert_ne!(vec3!(0.0).hash_bits(), vec3!(-0.0, 0.0, 0.0).hash_bits());
+        assert_eq!(
+            Vec4::new(f32::NAN, 0.0, 0.0, 1.0).hash_bits(),
+            Vec4::new(f32::NAN, 0.0, 0.0, 1.0).hash_bits()
+        );
+        assert_eq!(
+            Vec4::new(0.0, 0.0, 0.0, 1.0).hash_bits(),
+            u128::from(1.0f32.to_bits()) << 96
+        );
+
+        let mut grid = HashMap::new();
+        grid.insert(vec3!(1.0, 2.0, 3.0).hash_bits(), 'a');
+        grid.insert(vec3!(3.0, 2.0, 1.0).hash_bits(), 'b');
+        assert_eq!(grid[&vec3!(1.0, 2.0, 3.0).hash_bits()], 'a');
+        assert_eq!(grid.len(), 2);
+    }
+
+    #[test]
+    pub fn vec_ref_ops() {
+        use super::{DVec4, Vec2};
+        fn sum_and_difference<'a, T>(a: &'a T, b: &'a T) -> (T, T)
+        where
+            &'a T: std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+        {
+            (a + b, a - b)
+        }
+
+        let (a, b) = (vec3!(1.0, 2.0, 3.0), vec3!(-4.0, 0.5, 2.0));
+        assert_eq!(sum_and_difference(&a, &b), (a + b, a - b));
+        let ra = &a;
+        assert_eq!(ra * 2.0, a * 2.0);
+        assert_eq!(ra / 4.0, a / 4.0);
+
+        let (c, d) = (Vec2::new(1.0, 2.0), Vec2::new(3.0, 5.0));
+        assert_eq!(sum_and_difference(&c, &d), (c + d, c - d));
+
+        let e = DVec4::new(1.0, -2.0, 3.0, -4.0);
+        let re = &e;
+        assert_eq!(re + re, e * 2.0);
+        assert_eq!(re - re, DVec4::zero());
+        assert_eq!(re * 0.5, e / 2.0);
+    }
+
+    #[test]
+    pub fn vec_neg() {
+        use super::{DVec4, Vec2};
+        assert_eq!(-Vec2::new(1.0, -2.0), Vec2::new(-1.0, 2.0));
+        assert_eq!(-vec3!(1.0, 0.0, -3.0), vec3!(-1.0, -0.0, 3.0));
+        assert_eq!(
+            -DVec4::new(1.0, 2.0, 3.0, 4.0),
+            DVec4::new(1.0, 2.0, 3.0, 4.0) * -1.0
+        );
+    }
+
+    #[test]
+    pub fn vec_as_mut() {
+        use super::{DVec2, Vec4};
+        let mut v = Vec4::zero();
+        v.as_mut().copy_from_slice(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!((v.x, v.y, v.z, v.w), (1.0, 2.0, 3.0, 4.0));
+        let mut v = DVec2::new(1.0, 2.0);
+        let a: &mut [f64; 2] = v.as_mut();
+        a[1] = -5.0;
+        assert_eq!(v.y, -5.0);
+    }
+
+    #[test]
+    pub fn vec_abs_diff() {
+        use super::{DVec2, Vec4};
+        let a = vec3!(1.0, 2.0, 3.0);
+        let b = vec3!(1.5, -1.0, 3.25);
+        assert_eq!(a.abs_diff(b), vec3!(0.5, 3.0, 0.25));
+        assert_eq!(b.abs_diff(a), vec3!(0.5, 3.0, 0.25));
+        assert_eq!(a.max_abs_diff(b), 3.0);
+        assert_eq!(a.max_abs_diff(a), 0.0);
+        assert_eq!(
+            Vec4::new(0.0, 0.0, 0.0, 1.0).max_abs_diff(Vec4::new(0.1, -0.2, 0.0, -1.0)),
+            2.0
+        );
+        assert_eq!(
+            DVec2::new(-4.0, 1.0).max_abs_diff(DVec2::new(-4.5, 1.0)),
+            0.5
+        );
+        assert!(a.max_abs_diff(vec3!(f32::NAN, 2.0, 3.0)).is_nan());
+        assert!(a.max_abs_diff(vec3!(1.0, 2.0, f32::NAN)).is_nan());
+        assert!(DVec2::new(f64::NAN, 0.0)
+            .max_abs_diff(DVec2::new(0.0, 5.0))
+            .is_nan());
+    }
+
+    #[test]
+    pub fn vec_face_forward() {
+        let n = vec3!(0.0, 1.0, 0.0);
+        assert_eq!(n.face_forward(vec3!(1.0, -1.0, 0.0), n), n);
+        assert_eq!(
+            n.face_forward(vec3!(1.0, 1.0, 0.0), n),
+            vec3!(0.0, -1.0, 0.0)
+        );
+        assert_eq!(
+            n.face_forward(vec3!(1.0, 0.0, 0.0), n),
+            vec3!(0.0, -1.0, 0.0)
+        );
+
+        let n = dvec3!(1.0, 2.0, 3.0);
+        let reference = dvec3!(0.0, 0.0, -1.0);
+        assert_eq!(n.face_forward(dvec3!(0.0, 0.0, 1.0), reference), n);
+        assert_eq!(n.face_forward(dvec3!(0.0, 0.0, -1.0), reference), -n);
+    }
+
+    #[test]
+    pub fn vec_snap() {
+        use super::{DVec2, Vec2};
+        assert_eq!(
+            Vec2::new(1.2, 3.7).snap(Vec2::new(1.0, 0.5)),
+            Vec2::new(1.0, 3.5)
+        );
+        assert_eq!(
+            vec3!(-1.3, 2.25, 7.77).snap(vec3!(1.0, 0.0, 0.25)),
+            vec3!(-1.0, 2.25, 7.75)
+        );
+        assert_eq!(
+            DVec2::new(14.0, -6.0).snap(DVec2::new(10.0, 4.0)),
+            DVec2::new(10.0, -8.0)
+        );
+    }
+
+    #[test]
+    pub fn vec4_point_direction() {
+        use super::{DVec4, Vec4};
+        assert_eq!(Vec4::point(vec3!(1.0, 2.0, 3.0)), vec4!(1.0, 2.0, 3.0, 1.0));
+        assert_eq!(
+            Vec4::direction(vec3!(1.0, 2.0, 3.0)),
+            vec4!(1.0, 2.0, 3.0, 0.0)
+        );
+        assert_eq!(DVec4::point(dvec3!(-1.0)).w, 1.0);
+        assert_eq!(DVec4::direction(dvec3!(-1.0)).w, 0.0);
+    }
+
+    #[test]
+    pub fn vec_shuffle() {
+        use super::{DVec3, Vec4};
+        assert_eq!(
+            vec3!(1.0, 2.0, 3.0).shuffle([2, 0, 1]),
+            vec3!(3.0, 1.0, 2.0)
+        );
+        assert_eq!(vec3!(1.0, 2.0, 3.0).shuffle([1, 1, 1]), vec3!(2.0));
+        assert_eq!(
+            Vec4::new(1.0, 2.0, 3.0, 4.0).shuffle([3, 2, 1, 0]),
+            Vec4::new(4.0, 3.0, 2.0, 1.0)
+        );
+        // Swapping the Y and Z axes.
+        assert_eq!(
+            DVec3::new(1.0, 2.0, 3.0).shuffle([0, 2, 1]),
+            DVec3::new(1.0, 3.0, 2.0)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn vec_shuffle_out_of_range() {
+        let _ = vec3!(1.0, 2.0, 3.0).shuffle([0, 1, 3]);
+    }
+
+    #[test]
+    pub fn vec3_spherical() {
+        use super::{DVec3, Vec3};
+        use std::f32::consts::{FRAC_PI_2, PI};
+        let (r, azimuth, inclination) = vec3!(0.0, 0.0, 2.0).to_spherical();
+        approx::assert_relative_eq!(r, 2.0);
+        approx::assert_relative_eq!(azimuth, FRAC_PI_2);
+        approx::assert_relative_eq!(inclination, FRAC_PI_2);
+        assert_eq!(vec3!(0.0, 3.0, 0.0).to_spherical(), (3.0, 0.0, 0.0));
+        assert_eq!(vec3!(0.0, -3.0, 0.0).to_spherical(), (3.0, 0.0, PI));
+        assert_eq!(vec3!(0.0).to_spherical(), (0.0, 0.0, 0.0));
+        approx::assert_relative_eq!(
+            Vec3::from_spherical(2.0, 0.0, FRAC_PI_2),
+            vec3!(2.0, 0.0, 0.0),
+            epsilon = 1.0e-6
+        );
+
+        let directions = [
+            dvec3!(1.0, 2.0, 3.0),
+            dvec3!(-4.0, 0.5, -0.25),
+            dvec3!(0.0, 1.0, 0.0),
+            dvec3!(0.0, -2.0, 0.0),
+            dvec3!(1.0e-9, 5.0, 0.0),
+            dvec3!(0.0, -5.0, -1.0e-9),
+        ];
+        for &v in &directions {
+            let (r, azimuth, inclination) = v.to_spherical();
+            approx::assert_relative_eq!(
+                DVec3::from_spherical(r, azimuth, inclination),
+                v,
+                epsilon = 1.0e-12
+            );
+        }
+    }
+
+    #[test]
+    pub fn vec2_polar() {
+        use super::{DVec2, Vec2};
+        use std::f32::consts::{FRAC_PI_2, PI};
+        assert_eq!(Vec2::zero().to_polar(), (0.0, 0.0));
+        assert_eq!(Vec2::new(0.0, 3.0).to_polar(), (3.0, FRAC_PI_2));
+        assert_eq!(Vec2::new(-2.0, 0.0).to_polar(), (2.0, PI));
+        approx::assert_relative_eq!(
+            Vec2::from_polar(2.0, FRAC_PI_2),
+            Vec2::new(0.0, 2.0),
+            epsilon = 1.0e-6
+        );
+
+        let points = [
+            DVec2::new(1.0, 2.0),
+            DVec2::new(-3.0, 0.5),
+            DVec2::new(-0.25, -4.0),
+            DVec2::new(7.0, 0.0),
+        ];
+        for &p in &points {
+            let (radius, angle) = p.to_polar();
+            approx::assert_relative_eq!(DVec2::from_polar(radius, angle), p, epsilon = 1.0e-12);
+        }
+        assert_eq!(DVec2::from_polar(0.0, 0.0), DVec2::zero());
+    }
+
+    #[test]
+    pub fn vec_cross_ref_and_hadamard() {
+        use super::{DVec3, Vec2, Vec4};
+        let (a, b) = (vec3!(1.0, 2.0, 3.0), vec3!(-4.0, 0.5, 2.0));
+        assert_eq!(a.cross_ref(&b), a.cross(b));
+        let (a, b) = (DVec3::new(0.1, -2.0, 7.0), DVec3::new(3.0, 1.0, -1.0));
+        assert_eq!(a.cross_ref(&b), a.cross(b));
+
+        assert_eq!(
+            vec3!(1.0, 2.0, 3.0) * vec3!(4.0, -1.0, 0.5),
+            vec3!(4.0, -2.0, 1.5)
+        );
+        let mut v = Vec4::new(1.0, 2.0, 3.0, 4.0);
+        v *= Vec4::new(2.0, 0.0, -1.0, 0.25);
+        assert_eq!(v, Vec4::new(2.0, 0.0, -3.0, 1.0));
+        let mut v = Vec2::new(3.0, 5.0);
+        v *= Vec2::new(1.0, 2.0);
+        assert_eq!(v, Vec2::new(3.0, 10.0));
+    }
 }
 
 #[cfg(feature = "mint")]